@@ -0,0 +1,82 @@
+//! Reads the posts in `content/blog`, fails the build if any of them is
+//! broken, and generates the post index, bodies and search index the crate
+//! embeds, along with resized variants of the images in `public/blog`.
+
+#[path = "src/blog/fnv.rs"]
+mod fnv;
+#[path = "build/front_matter.rs"]
+mod front_matter;
+#[path = "build/images.rs"]
+mod images;
+#[path = "build/search.rs"]
+mod search;
+#[path = "build/validate.rs"]
+mod validate;
+#[path = "src/blog/words.rs"]
+mod words;
+
+use std::path::PathBuf;
+use std::{env, fs};
+
+fn main() {
+    println!("cargo::rerun-if-changed=build");
+    println!("cargo::rerun-if-changed=content/blog");
+    println!("cargo::rerun-if-changed=public/blog");
+    println!("cargo::rerun-if-changed=src/blog/fnv.rs");
+    println!("cargo::rerun-if-changed=src/blog/words.rs");
+
+    let root = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    let mut paths: Vec<PathBuf> = fs::read_dir(root.join("content/blog"))
+        .expect("content/blog should exist")
+        .map(|entry| entry.expect("readable content/blog entry").path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "md"))
+        .collect();
+    paths.sort();
+    let files: Vec<(String, String)> = paths
+        .iter()
+        .map(|path| {
+            let name = path.file_name().unwrap().to_string_lossy().into_owned();
+            (name, fs::read_to_string(path).expect("readable post"))
+        })
+        .collect();
+    let mut posts = validate::posts(&files, &root.join("public"))
+        .unwrap_or_else(|errors| panic!("broken blog posts:\n{}", errors.join("\n")));
+
+    // Newest first, the order every listing shows them in.
+    posts.sort_by(|(a, _), (b, _)| b.date.cmp(&a.date).then(a.slug.cmp(&b.slug)));
+
+    // The browser only gets the posts that are out no matter the date, see
+    // `Post::is_always_public`. `get_visibility` sends it the others.
+    let mut index = String::from("pub static POST_INDEX: PostIndex = PostIndex::new(&[\n");
+    let mut public_index = index.clone();
+    let mut bodies = String::from("pub static BODIES: &[(&str, &str)] = &[\n");
+    for (meta, body) in &posts {
+        let post = format!(
+            concat!(
+                "    Post {{ slug: {:?}, title: {:?}, date: {:?}, tags: &{:?}, summary: {:?}, ",
+                "draft: {}, publish_at: {:?} }},\n",
+            ),
+            meta.slug, meta.title, meta.date, meta.tags, meta.summary, meta.draft, meta.publish_at
+        );
+        if !meta.draft && meta.publish_at.is_none() {
+            public_index.push_str(&post);
+        }
+        index.push_str(&post);
+        bodies.push_str(&format!("    ({:?}, {body:?}),\n", meta.slug));
+    }
+    index.push_str("]);\n");
+    public_index.push_str("]);\n");
+    bodies.push_str("];\n");
+
+    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+    fs::write(out_dir.join("post_index.rs"), index).expect("writable OUT_DIR");
+    fs::write(out_dir.join("public_post_index.rs"), public_index).expect("writable OUT_DIR");
+    fs::write(out_dir.join("post_bodies.rs"), bodies).expect("writable OUT_DIR");
+    fs::write(out_dir.join("search_index.rs"), search::index(&posts)).expect("writable OUT_DIR");
+
+    // Only the server serves the variants, the WASM bundle just links to them.
+    let encode = env::var_os("CARGO_FEATURE_SSR").is_some();
+    let (image_index, image_files) = images::generate(&root.join("public"), &out_dir, encode);
+    fs::write(out_dir.join("image_index.rs"), image_index).expect("writable OUT_DIR");
+    fs::write(out_dir.join("image_files.rs"), image_files).expect("writable OUT_DIR");
+}
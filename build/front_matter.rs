@@ -0,0 +1,193 @@
+use thiserror::Error;
+
+/// Metadata at the top of a post, between two `---` lines.
+///
+/// Only a small subset of YAML is understood: `key: value` pairs, optionally
+/// quoted, and `[a, b]` lists.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FrontMatter {
+    pub title: String,
+    /// `YYYY-MM-DD`.
+    pub date: String,
+    pub slug: String,
+    pub tags: Vec<String>,
+    pub summary: String,
+    /// Hidden everywhere except in previews.
+    pub draft: bool,
+    /// `YYYY-MM-DD` before which the post is hidden like a draft.
+    pub publish_at: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum FrontMatterError {
+    #[error("missing front matter, the file must start with ---")]
+    Missing,
+    #[error("front matter is not closed with ---")]
+    Unterminated,
+    #[error("line {0}: expected `key: value`")]
+    Malformed(usize),
+    #[error("unknown field `{0}`")]
+    UnknownField(String),
+    #[error("missing required field `{0}`")]
+    MissingField(&'static str),
+    #[error("`{0}` must be true or false")]
+    NotABool(&'static str),
+}
+
+/// Splits a post into its front matter and Markdown body.
+pub fn parse(source: &str) -> Result<(FrontMatter, &str), FrontMatterError> {
+    let rest = source
+        .strip_prefix("---")
+        .and_then(|rest| {
+            rest.strip_prefix('\n')
+                .or_else(|| rest.strip_prefix("\r\n"))
+        })
+        .ok_or(FrontMatterError::Missing)?;
+
+    let mut offset = 0;
+    let mut split = None;
+    for line in rest.split_inclusive('\n') {
+        if line.trim_end() == "---" {
+            split = Some((&rest[..offset], &rest[offset + line.len()..]));
+            break;
+        }
+        offset += line.len();
+    }
+    let (header, body) = split.ok_or(FrontMatterError::Unterminated)?;
+
+    let (mut title, mut date, mut slug, mut tags, mut summary) = (None, None, None, None, None);
+    let (mut draft, mut publish_at) = (false, None);
+    for (i, line) in header.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        // Line 1 is the opening `---`.
+        let (key, value) = line
+            .split_once(':')
+            .ok_or(FrontMatterError::Malformed(i + 2))?;
+        let value = value.trim();
+        match key.trim() {
+            "title" => title = Some(unquote(value)),
+            "date" => date = Some(unquote(value)),
+            "slug" => slug = Some(unquote(value)),
+            "summary" => summary = Some(unquote(value)),
+            "tags" => tags = Some(list(value)),
+            "draft" => {
+                draft = match unquote(value).as_str() {
+                    "true" => true,
+                    "false" => false,
+                    _ => return Err(FrontMatterError::NotABool("draft")),
+                }
+            }
+            "publish_at" => publish_at = Some(unquote(value)),
+            key => return Err(FrontMatterError::UnknownField(key.to_string())),
+        }
+    }
+
+    let front_matter = FrontMatter {
+        title: title.ok_or(FrontMatterError::MissingField("title"))?,
+        date: date.ok_or(FrontMatterError::MissingField("date"))?,
+        slug: slug.ok_or(FrontMatterError::MissingField("slug"))?,
+        tags: tags.unwrap_or_default(),
+        summary: summary.ok_or(FrontMatterError::MissingField("summary"))?,
+        draft,
+        publish_at,
+    };
+    Ok((front_matter, body))
+}
+
+fn unquote(value: &str) -> String {
+    ['"', '\'']
+        .iter()
+        .find_map(|quote| {
+            value
+                .strip_prefix(*quote)
+                .and_then(|value| value.strip_suffix(*quote))
+        })
+        .unwrap_or(value)
+        .to_string()
+}
+
+/// `[a, "b c"]`, or a single bare value.
+fn list(value: &str) -> Vec<String> {
+    let inner = value
+        .strip_prefix('[')
+        .and_then(|value| value.strip_suffix(']'))
+        .unwrap_or(value);
+    inner
+        .split(',')
+        .map(|item| unquote(item.trim()))
+        .filter(|item| !item.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_fields_and_body() {
+        let source = "---\n\
+            title: \"Hello: world\"\n\
+            # A comment\n\
+            date: 2024-06-20\n\
+            slug: 'hello'\n\
+            tags: [rust, \"web dev\", ]\n\
+            summary: Hi\n\
+            draft: true\n\
+            publish_at: 2024-07-01\n\
+            ---\n\
+            # Body\n";
+        let (meta, body) = parse(source).unwrap();
+        assert_eq!(
+            meta,
+            FrontMatter {
+                title: "Hello: world".to_string(),
+                date: "2024-06-20".to_string(),
+                slug: "hello".to_string(),
+                tags: vec!["rust".to_string(), "web dev".to_string()],
+                summary: "Hi".to_string(),
+                draft: true,
+                publish_at: Some("2024-07-01".to_string()),
+            }
+        );
+        assert_eq!(body, "# Body\n");
+    }
+
+    #[test]
+    fn defaults_optional_fields() {
+        let source = "---\r\ntitle: a\r\ndate: 2024-06-20\r\nslug: a\r\nsummary: b\r\n---\r\nbody";
+        let (meta, body) = parse(source).unwrap();
+        assert!(meta.tags.is_empty());
+        assert!(!meta.draft);
+        assert_eq!(meta.publish_at, None);
+        assert_eq!(body, "body");
+        assert_eq!(list("rust"), ["rust"]);
+    }
+
+    #[test]
+    fn rejects_broken_front_matter() {
+        assert_eq!(parse("title: a\n"), Err(FrontMatterError::Missing));
+        assert_eq!(
+            parse("---\ntitle: a\n"),
+            Err(FrontMatterError::Unterminated)
+        );
+        assert_eq!(
+            parse("---\ntitle: a\nno colon\n---\n"),
+            Err(FrontMatterError::Malformed(3))
+        );
+        assert_eq!(
+            parse("---\ntitle: a\nauthor: b\n---\n"),
+            Err(FrontMatterError::UnknownField("author".to_string()))
+        );
+        assert_eq!(
+            parse("---\ntitle: a\nslug: a\nsummary: b\n---\n"),
+            Err(FrontMatterError::MissingField("date"))
+        );
+        assert_eq!(
+            parse("---\ntitle: a\ndraft: yes\n---\n"),
+            Err(FrontMatterError::NotABool("draft"))
+        );
+    }
+}
@@ -0,0 +1,225 @@
+use std::fs;
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+
+use image::codecs::avif::AvifEncoder;
+use image::imageops::FilterType;
+use image::{DynamicImage, ExtendedColorType, ImageDecoder, ImageEncoder, ImageReader};
+
+use crate::fnv::fnv1a;
+
+/// Widths of the resized variants. Narrower images get one at their own width.
+const WIDTHS: &[u32] = &[480, 960, 1440];
+const PLACEHOLDER_WIDTH: u32 = 16;
+const WEBP_QUALITY: f32 = 75.0;
+const AVIF_QUALITY: u8 = 60;
+/// rav1e's fastest preset. Slower ones save a few percent and take minutes.
+const AVIF_SPEED: u8 = 10;
+/// Bumped when the encoding changes, so cached variants are made again.
+const VERSION: u32 = 1;
+
+/// Where the server serves the variants, see `IMAGE_FILES`.
+const URL_PREFIX: &str = "/blog/img/";
+
+/// Generates `IMAGE_INDEX` for every JPEG and PNG in a `public/blog` folder.
+/// With `encode`, also writes the variants to `out_dir/images` and generates
+/// `IMAGE_FILES` embedding them, otherwise `IMAGE_FILES` is empty. Variants
+/// made by an earlier build are reused.
+pub fn generate(public: &Path, out_dir: &Path, encode: bool) -> (String, String) {
+    let images_dir = out_dir.join("images");
+    fs::create_dir_all(&images_dir).expect("writable OUT_DIR");
+
+    let mut index = String::from("pub static IMAGE_INDEX: &[ResponsiveImage] = &[\n");
+    let mut files = String::from("pub static IMAGE_FILES: &[(&str, &[u8])] = &[\n");
+    for path in sources(&public.join("blog")) {
+        let src = format!(
+            "/{}",
+            path.strip_prefix(public)
+                .unwrap()
+                .to_string_lossy()
+                .replace('\\', "/")
+        );
+        let bytes = fs::read(&path).expect("readable image");
+        let image = decode(&bytes).unwrap_or_else(|err| panic!("public{src}: {err}"));
+
+        let mut widths: Vec<u32> = WIDTHS
+            .iter()
+            .copied()
+            .filter(|&width| width < image.width())
+            .collect();
+        if image.width() <= *WIDTHS.last().unwrap() {
+            widths.push(image.width());
+        }
+        let stem = path.file_stem().unwrap().to_string_lossy();
+        let name = format!("{stem}-{:016x}", fingerprint(&bytes));
+
+        if encode {
+            for &width in &widths {
+                let resized = image.resize(width, u32::MAX, FilterType::Lanczos3);
+                for (ext, encode) in [
+                    ("avif", avif as fn(&DynamicImage) -> Vec<u8>),
+                    ("webp", webp),
+                ] {
+                    let file = format!("{name}-{width}.{ext}");
+                    let out = images_dir.join(&file);
+                    if !out.is_file() {
+                        fs::write(&out, encode(&resized)).expect("writable OUT_DIR");
+                    }
+                    files.push_str(&format!(
+                        "    ({file:?}, include_bytes!({:?})),\n",
+                        out.to_string_lossy()
+                    ));
+                }
+            }
+        }
+
+        let placeholder = webp(&image.resize(PLACEHOLDER_WIDTH, u32::MAX, FilterType::Triangle));
+        index.push_str(&format!(
+            concat!(
+                "    ResponsiveImage {{ src: {:?}, width: {}, height: {}, variants: {:?}, ",
+                "widths: &{:?}, placeholder: {:?} }},\n",
+            ),
+            src,
+            image.width(),
+            image.height(),
+            format!("{URL_PREFIX}{name}"),
+            widths,
+            format!("data:image/webp;base64,{}", base64(&placeholder)),
+        ));
+    }
+    index.push_str("];\n");
+    files.push_str("];\n");
+    (index, files)
+}
+
+/// The JPEGs and PNGs in the folders of `blog`, sorted.
+fn sources(blog: &Path) -> Vec<PathBuf> {
+    let mut paths: Vec<PathBuf> = fs::read_dir(blog)
+        .into_iter()
+        .flatten()
+        .flat_map(|folder| fs::read_dir(folder.expect("readable public/blog entry").path()))
+        .flatten()
+        .map(|entry| entry.expect("readable public/blog entry").path())
+        .filter(|path| {
+            path.extension()
+                .and_then(|ext| ext.to_str())
+                .is_some_and(|ext| {
+                    ["jpg", "jpeg", "png"].contains(&ext.to_ascii_lowercase().as_str())
+                })
+        })
+        .collect();
+    paths.sort();
+    paths
+}
+
+/// Decodes `bytes`, turned upright the way browsers show the original.
+fn decode(bytes: &[u8]) -> image::ImageResult<DynamicImage> {
+    let mut decoder = ImageReader::new(Cursor::new(bytes))
+        .with_guessed_format()?
+        .into_decoder()?;
+    let orientation = decoder.orientation()?;
+    let mut image = DynamicImage::from_decoder(decoder)?;
+    image.apply_orientation(orientation);
+    Ok(image)
+}
+
+fn avif(image: &DynamicImage) -> Vec<u8> {
+    let mut out = Vec::new();
+    let encoder = AvifEncoder::new_with_speed_quality(&mut out, AVIF_SPEED, AVIF_QUALITY);
+    let result = if image.color().has_alpha() {
+        let rgba = image.to_rgba8();
+        encoder.write_image(&rgba, rgba.width(), rgba.height(), ExtendedColorType::Rgba8)
+    } else {
+        let rgb = image.to_rgb8();
+        encoder.write_image(&rgb, rgb.width(), rgb.height(), ExtendedColorType::Rgb8)
+    };
+    result.expect("AVIF encoding of a decoded image");
+    out
+}
+
+fn webp(image: &DynamicImage) -> Vec<u8> {
+    if image.color().has_alpha() {
+        let rgba = image.to_rgba8();
+        webp::Encoder::from_rgba(&rgba, rgba.width(), rgba.height())
+            .encode(WEBP_QUALITY)
+            .to_vec()
+    } else {
+        let rgb = image.to_rgb8();
+        webp::Encoder::from_rgb(&rgb, rgb.width(), rgb.height())
+            .encode(WEBP_QUALITY)
+            .to_vec()
+    }
+}
+
+/// FNV-1a of the encoding settings and the source image, naming its variants.
+fn fingerprint(bytes: &[u8]) -> u64 {
+    fnv1a(VERSION.to_le_bytes().iter().chain(bytes))
+}
+
+/// Standard base64 with padding, for data URLs.
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let group = chunk.iter().enumerate().fold(0u32, |group, (i, byte)| {
+            group | (u32::from(*byte) << (16 - 8 * i))
+        });
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[((group >> (18 - 6 * i)) & 0x3f) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pads_base64() {
+        assert_eq!(base64(b""), "");
+        assert_eq!(base64(b"f"), "Zg==");
+        assert_eq!(base64(b"fo"), "Zm8=");
+        assert_eq!(base64(b"foo"), "Zm9v");
+        assert_eq!(base64(b"foob"), "Zm9vYg==");
+        assert_eq!(base64(&[0xfb, 0xff, 0xbf]), "+/+/");
+    }
+
+    #[test]
+    fn fingerprints_the_version_and_bytes() {
+        assert_eq!(fnv1a(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(fnv1a(b"a"), 0xaf63_dc4c_8601_ec8c);
+        assert_eq!(fingerprint(b"image"), fingerprint(b"image"));
+        assert_ne!(fingerprint(b"image"), fingerprint(b"imagf"));
+        assert_ne!(fingerprint(b"image"), fnv1a(b"image"));
+    }
+
+    #[test]
+    fn indexes_images_without_encoding() {
+        let dir = std::env::temp_dir().join(format!("rabbitnook-images-{}", std::process::id()));
+        let public = dir.join("public");
+        let post = public.join("blog/post");
+        fs::create_dir_all(&post).unwrap();
+        image::RgbImage::new(20, 10)
+            .save(post.join("photo.png"))
+            .unwrap();
+        fs::write(post.join("notes.txt"), "not an image").unwrap();
+
+        let (index, files) = generate(&public, &dir.join("out"), false);
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(files, "pub static IMAGE_FILES: &[(&str, &[u8])] = &[\n];\n");
+        let lines: Vec<&str> = index.lines().collect();
+        assert_eq!(lines.len(), 3, "{index}");
+        assert!(lines[1].starts_with(concat!(
+            r#"    ResponsiveImage { src: "/blog/post/photo.png", width: 20, height: 10, "#,
+            r#"variants: "/blog/img/photo-"#,
+        )));
+        assert!(lines[1].contains(r#"widths: &[20], placeholder: "data:image/webp;base64,"#));
+    }
+}
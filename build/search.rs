@@ -0,0 +1,76 @@
+use std::collections::BTreeMap;
+
+use pulldown_cmark::{Event, Options, Parser, TagEnd};
+
+use crate::front_matter::FrontMatter;
+use crate::words::{normalize, words};
+
+/// How much an occurrence counts for, by where in the post it is.
+const TITLE_WEIGHT: u32 = 5;
+const TAG_WEIGHT: u32 = 3;
+const SUMMARY_WEIGHT: u32 = 2;
+const BODY_WEIGHT: u32 = 1;
+
+/// Generates `SEARCH_INDEX` for `posts`, which must be in `POST_INDEX` order.
+/// Drafts and scheduled posts are indexed too. The index stays on the server,
+/// which hides them at search time, so a scheduled post turns up on its
+/// publish date without a rebuild.
+pub fn index(posts: &[(FrontMatter, String)]) -> String {
+    let mut terms: BTreeMap<String, BTreeMap<usize, u32>> = BTreeMap::new();
+    let mut texts = Vec::new();
+    for (post, (meta, body)) in posts.iter().enumerate() {
+        let text = format!("{} {}", meta.summary, plain_text(body));
+        let mut add = |text: &str, weight: u32| {
+            for (_, word) in words(text) {
+                *terms
+                    .entry(normalize(word))
+                    .or_default()
+                    .entry(post)
+                    .or_default() += weight;
+            }
+        };
+        add(&meta.title, TITLE_WEIGHT);
+        for tag in &meta.tags {
+            add(tag, TAG_WEIGHT);
+        }
+        add(&meta.summary, SUMMARY_WEIGHT);
+        add(&text[meta.summary.len()..], BODY_WEIGHT);
+        texts.push(text);
+    }
+
+    let mut out =
+        String::from("pub static SEARCH_INDEX: SearchIndex = SearchIndex::new(\n    &[\n");
+    for text in &texts {
+        out.push_str(&format!("        {text:?},\n"));
+    }
+    out.push_str("    ],\n    &[\n");
+    for (term, postings) in &terms {
+        let postings: Vec<String> = postings
+            .iter()
+            .map(|(post, score)| format!("({post}, {score})"))
+            .collect();
+        out.push_str(&format!(
+            "        ({term:?}, &[{}]),\n",
+            postings.join(", ")
+        ));
+    }
+    out.push_str("    ],\n);\n");
+    out
+}
+
+/// The text of a Markdown body without markup, on one line so snippets read
+/// as running text.
+fn plain_text(body: &str) -> String {
+    let mut text = String::new();
+    for event in Parser::new_ext(body, Options::ENABLE_HEADING_ATTRIBUTES) {
+        match event {
+            Event::Text(part) | Event::Code(part) => text.push_str(&part),
+            Event::SoftBreak | Event::HardBreak => text.push(' '),
+            Event::End(
+                TagEnd::Paragraph | TagEnd::Heading(_) | TagEnd::CodeBlock | TagEnd::Item,
+            ) => text.push(' '),
+            _ => {}
+        }
+    }
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
@@ -0,0 +1,258 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use pulldown_cmark::{Event, Parser, Tag};
+
+use crate::front_matter::{self, FrontMatter};
+
+/// Paths under `/blog` that are routes rather than posts.
+const RESERVED_SLUGS: &[&str] = &["search", "tags"];
+
+/// Parses the posts in `content/blog`, given as file name and contents, with
+/// everything wrong with any of them as `Err`.
+pub fn posts(
+    files: &[(String, String)],
+    public: &Path,
+) -> Result<Vec<(FrontMatter, String)>, Vec<String>> {
+    let mut posts = Vec::new();
+    let mut errors = Vec::new();
+    let mut slugs = HashMap::new();
+    for (name, source) in files {
+        let stem = name.strip_suffix(".md").unwrap_or(name);
+        let (meta, body) = match front_matter::parse(source) {
+            Ok(parsed) => parsed,
+            Err(err) => {
+                errors.push(format!("content/blog/{name}: {err}"));
+                continue;
+            }
+        };
+
+        for error in post(stem, &meta, body, public) {
+            errors.push(format!("content/blog/{name}: {error}"));
+        }
+        if let Some(other) = slugs.insert(meta.slug.clone(), name) {
+            errors.push(format!(
+                "content/blog/{name}: slug `{}` is already used by {other}",
+                meta.slug
+            ));
+        }
+        posts.push((meta, body.to_string()));
+    }
+
+    if errors.is_empty() {
+        Ok(posts)
+    } else {
+        Err(errors)
+    }
+}
+
+/// Everything wrong with one post that would otherwise only show up at runtime.
+pub fn post(file_stem: &str, meta: &FrontMatter, body: &str, public: &Path) -> Vec<String> {
+    let mut errors = Vec::new();
+
+    if meta.title.trim().is_empty() {
+        errors.push("`title` is empty".to_string());
+    }
+    if meta.summary.trim().is_empty() {
+        errors.push("`summary` is empty".to_string());
+    }
+    if !is_slug(&meta.slug) {
+        errors.push(format!(
+            "slug `{}` may only contain a-z, 0-9 and -",
+            meta.slug
+        ));
+    }
+    if RESERVED_SLUGS.contains(&meta.slug.as_str()) {
+        errors.push(format!(
+            "slug `{}` is taken by a page under /blog",
+            meta.slug
+        ));
+    }
+    for tag in meta.tags.iter().filter(|tag| !is_slug(tag)) {
+        errors.push(format!("tag `{tag}` may only contain a-z, 0-9 and -"));
+    }
+    if meta.slug != file_stem {
+        errors.push(format!(
+            "slug `{}` does not match the file name `{file_stem}.md`",
+            meta.slug
+        ));
+    }
+    if !is_iso_date(&meta.date) {
+        errors.push(format!(
+            "date `{}` is not a valid YYYY-MM-DD date",
+            meta.date
+        ));
+    }
+    if let Some(publish_at) = meta.publish_at.as_ref().filter(|at| !is_iso_date(at)) {
+        errors.push(format!(
+            "publish_at `{publish_at}` is not a valid YYYY-MM-DD date"
+        ));
+    }
+
+    let folder = format!("/blog/{}/", meta.slug);
+    for event in Parser::new(body) {
+        let Event::Start(Tag::Image { dest_url, .. }) = event else {
+            continue;
+        };
+        if dest_url.contains("://") || dest_url.starts_with("//") {
+            continue;
+        }
+        if !dest_url.starts_with(&folder) {
+            errors.push(format!("image `{dest_url}` must be under {folder}"));
+        } else if !public.join(&dest_url[1..]).is_file() {
+            errors.push(format!(
+                "image `{dest_url}` does not exist in public{dest_url}"
+            ));
+        }
+    }
+
+    errors
+}
+
+/// A real calendar date written as `YYYY-MM-DD`.
+pub fn is_iso_date(date: &str) -> bool {
+    let bytes = date.as_bytes();
+    if bytes.len() != 10
+        || bytes[4] != b'-'
+        || bytes[7] != b'-'
+        || !date
+            .bytes()
+            .enumerate()
+            .all(|(i, byte)| i == 4 || i == 7 || byte.is_ascii_digit())
+    {
+        return false;
+    }
+
+    let year: u32 = date[..4].parse().unwrap_or(0);
+    let month: u32 = date[5..7].parse().unwrap_or(0);
+    let day: u32 = date[8..].parse().unwrap_or(0);
+    let leap = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+    let days = match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if leap => 29,
+        2 => 28,
+        _ => return false,
+    };
+    (1..=days).contains(&day)
+}
+
+/// Non-empty and only `a-z`, `0-9` and `-`, so it can be used in a URL as is.
+fn is_slug(value: &str) -> bool {
+    !value.is_empty()
+        && value
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn source(slug: &str, date: &str) -> String {
+        format!("---\ntitle: T\ndate: {date}\nslug: {slug}\ntags: [rust]\nsummary: S\n---\nBody\n")
+    }
+
+    fn check(files: &[(&str, String)]) -> Result<Vec<String>, Vec<String>> {
+        let files: Vec<(String, String)> = files
+            .iter()
+            .map(|(name, source)| (name.to_string(), source.clone()))
+            .collect();
+        posts(&files, Path::new("public"))
+            .map(|posts| posts.into_iter().map(|(meta, _)| meta.slug).collect())
+    }
+
+    #[test]
+    fn accepts_valid_posts() {
+        assert_eq!(
+            check(&[
+                ("a.md", source("a", "2024-02-29")),
+                ("b.md", source("b", "2000-02-29")),
+            ]),
+            Ok(vec!["a".to_string(), "b".to_string()])
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_dates() {
+        for date in [
+            "2023-02-29",
+            "1900-02-29",
+            "2024-13-01",
+            "2024-04-31",
+            "2024-6-20",
+            "20240620",
+            "",
+        ] {
+            assert!(!is_iso_date(date), "{date}");
+            assert_eq!(
+                check(&[("a.md", source("a", date))]),
+                Err(vec![format!(
+                    "content/blog/a.md: date `{date}` is not a valid YYYY-MM-DD date"
+                )])
+            );
+        }
+    }
+
+    #[test]
+    fn reports_missing_dates_and_unknown_keys() {
+        assert_eq!(
+            check(&[(
+                "a.md",
+                "---\ntitle: T\nslug: a\nsummary: S\n---\n".to_string()
+            )]),
+            Err(vec![
+                "content/blog/a.md: missing required field `date`".to_string()
+            ])
+        );
+        assert_eq!(
+            check(&[("a.md", source("a", "2024-06-20").replace("tags", "tag"))]),
+            Err(vec!["content/blog/a.md: unknown field `tag`".to_string()])
+        );
+    }
+
+    #[test]
+    fn rejects_duplicate_slugs() {
+        assert_eq!(
+            check(&[
+                ("a.md", source("a", "2024-06-20")),
+                ("b.md", source("a", "2024-06-21")),
+            ]),
+            Err(vec![
+                "content/blog/b.md: slug `a` does not match the file name `b.md`".to_string(),
+                "content/blog/b.md: slug `a` is already used by a.md".to_string(),
+            ])
+        );
+    }
+
+    #[test]
+    fn rejects_reserved_slugs() {
+        for slug in RESERVED_SLUGS {
+            assert_eq!(
+                check(&[(&format!("{slug}.md"), source(slug, "2024-06-20"))]),
+                Err(vec![format!(
+                    "content/blog/{slug}.md: slug `{slug}` is taken by a page under /blog"
+                )])
+            );
+        }
+    }
+
+    #[test]
+    fn checks_the_rest_of_a_post() {
+        let (meta, body) = front_matter::parse(
+            "---\ntitle: ' '\ndate: 2024-06-20\nslug: Hello\ntags: [Rust]\nsummary: S\n\
+             publish_at: soon\n---\n![a](/blog/other/a.png) ![b](https://example.com/b.png)\n",
+        )
+        .unwrap();
+        assert_eq!(
+            post("Hello", &meta, body, Path::new("public")),
+            [
+                "`title` is empty",
+                "slug `Hello` may only contain a-z, 0-9 and -",
+                "tag `Rust` may only contain a-z, 0-9 and -",
+                "publish_at `soon` is not a valid YYYY-MM-DD date",
+                "image `/blog/other/a.png` must be under /blog/Hello/",
+            ]
+        );
+    }
+}
@@ -1,11 +1,16 @@
-use crate::components::blogpages::esp32_relay::Esp32Relay;
-use crate::components::blogpages::nix_frame_hack::NixFrameHack;
+use crate::blog::{get_visibility, Visibility};
 use crate::components::{
     blog::Blog,
+    blog_post::BlogPost,
+    error_pages::{ErrorPage, NotFound},
     links::Links,
     name_header::NameHeader,
-    terminal::{TerminalHistory, TerminalInput},
+    search::Search,
+    seo::Seo,
+    tags::{TagPosts, Tags},
+    terminal::{load_session, TerminalHistory, TerminalInput},
 };
+use crate::site::{get_site_origin, SITE_NAME};
 use leptos::prelude::*;
 use leptos_meta::*;
 use leptos_router::components::{Route, Router, Routes};
@@ -20,6 +25,8 @@ pub fn shell(options: LeptosOptions) -> impl IntoView {
                 <meta name="viewport" content="width=device-width, initial-scale=1" />
                 <AutoReload options=options.clone() />
                 <HydrationScripts options />
+                <link rel="alternate" type="application/rss+xml" title="RabbitNook" href="/blog/rss.xml" />
+                <link rel="alternate" type="application/atom+xml" title="RabbitNook" href="/blog/atom.xml" />
                 <MetaTags />
             </head>
             <body>
@@ -33,6 +40,20 @@ pub fn shell(options: LeptosOptions) -> impl IntoView {
 pub fn App() -> impl IntoView {
     // Provides context that manages stylesheets, titles, meta tags, etc.
     provide_meta_context();
+    // Which posts the blog pages show, see `use_visibility`.
+    provide_context(Resource::new(
+        || (),
+        |_| async {
+            get_visibility()
+                .await
+                .unwrap_or_else(|_| Visibility::public())
+        },
+    ));
+    // Absolute URLs in `Seo`, see `use_site_origin`.
+    provide_context(Resource::new(
+        || (),
+        |_| async { get_site_origin().await.unwrap_or_default() },
+    ));
 
     view! {
         <Stylesheet id="leptos" href="/pkg/rabbitnook.css" />
@@ -42,26 +63,22 @@ pub fn App() -> impl IntoView {
             href="https://fonts.googleapis.com/css2?family=JetBrains+Mono:ital,wght@0,100..800;1,100..800&display=swap"
             rel="stylesheet"
         />
-        <Link
-            rel="stylesheet"
-            href="//unpkg.com/@catppuccin/highlightjs@0.2.2/css/catppuccin-mocha.css"
-        />
 
         // sets the document title
         <Title text="RabbitNook" />
 
         <Router>
             <main>
-                <Routes fallback=|| "Not Found">
-                    <Route path=path!("") view=HomePage />
-                    <Route path=path!("/blog") view=Blog ssr=SsrMode::Async />
-                    <Route path=path!("/blog/esp32-relay") view=Esp32Relay ssr=SsrMode::Async />
-                    <Route
-                        path=path!("/blog/nix-frame-hack")
-                        view=NixFrameHack
-                        ssr=SsrMode::Async
-                    />
-                </Routes>
+                <ErrorBoundary fallback=|errors| view! { <ErrorPage errors /> }>
+                    <Routes fallback=NotFound>
+                        <Route path=path!("") view=HomePage ssr=SsrMode::Async />
+                        <Route path=path!("/blog") view=Blog ssr=SsrMode::Async />
+                        <Route path=path!("/blog/search") view=Search ssr=SsrMode::Async />
+                        <Route path=path!("/blog/tags") view=Tags ssr=SsrMode::Async />
+                        <Route path=path!("/blog/tags/:tag") view=TagPosts ssr=SsrMode::Async />
+                        <Route path=path!("/blog/:slug") view=BlogPost ssr=SsrMode::Async />
+                    </Routes>
+                </ErrorBoundary>
             </main>
         </Router>
     }
@@ -70,11 +87,16 @@ pub fn App() -> impl IntoView {
 /// Renders the home page of your application.
 #[component]
 fn HomePage() -> impl IntoView {
-    let (command_history, set_command_history) = signal(vec![]);
-
     let input_element = NodeRef::new();
+    // Only visitors without WASM have a session saved on the server.
+    let saved_session = Resource::new(|| (), |_| load_session());
 
     view! {
+        <Seo
+            title=SITE_NAME
+            description="Oliver Säfström, fullstack developer. A blog about Rust, embedded and hardware hacking, and a terminal to explore."
+            path="/"
+        />
         <div class="flex md:flex-row flex-col min-h-screen w-full bg-base items-center justify-center">
             <div class="md:w-1/2 w-5/6 flex flex-col justify-center items-center">
                 <NameHeader />
@@ -93,18 +115,21 @@ fn HomePage() -> impl IntoView {
             </div>
             <section class="md:w-1/2 w-5/6 md:h-screen flex flex-col justify-center items-center">
                 <div
-                    class="flex flex-col border shadow-md shadow-black border-peach rounded-md bg-base w-full md:w-5/6 min-h-96 h-5/6"
+                    class="flex flex-col border shadow-md shadow-black border-peach rounded-md bg-base w-full md:w-5/6 min-h-96 h-5/6 max-h-[85vh]"
                     on:click=move |_| {
                         let _ = input_element.get().expect("Input shoud be there to focus").focus();
                     }
                 >
                     <p class="text-white m-2">"Type 'help' for available commands."</p>
-                    <TerminalHistory command_history=command_history />
-                    <TerminalInput
-                        input_element=input_element
-                        set_command_history=set_command_history
-                        command_history=command_history
-                    />
+                    <Suspense>
+                        {move || Suspend::new(async move {
+                            let session = RwSignal::new(saved_session.await.unwrap_or_default());
+                            view! {
+                                <TerminalHistory session=session />
+                                <TerminalInput input_element=input_element session=session />
+                            }
+                        })}
+                    </Suspense>
 
                 </div>
             </section>
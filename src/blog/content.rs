@@ -0,0 +1,70 @@
+use std::collections::HashMap;
+
+use lazy_static::lazy_static;
+use pulldown_cmark::{html, CowStr, Event, Options, Parser, Tag};
+
+use super::markdown::{self, Block};
+
+// Defines `BODIES`, the slug and Markdown body of every post, see `build.rs`.
+include!(concat!(env!("OUT_DIR"), "/post_bodies.rs"));
+
+lazy_static! {
+    /// Every post body, parsed once on first use.
+    static ref PARSED: HashMap<&'static str, Vec<Block>> = BODIES
+        .iter()
+        .map(|(slug, body)| (*slug, markdown::parse(body)))
+        .collect();
+}
+
+pub fn body(slug: &str) -> Option<Vec<Block>> {
+    PARSED.get(slug).cloned()
+}
+
+/// A post body as HTML for feeds, with site-relative links and images made
+/// absolute against `origin`.
+pub fn html(slug: &str, origin: &str) -> Option<String> {
+    let (_, body) = BODIES.iter().find(|(s, _)| *s == slug)?;
+    let absolute = |url: CowStr<'static>| -> CowStr<'static> {
+        if url.starts_with('/') {
+            format!("{origin}{url}").into()
+        } else {
+            url
+        }
+    };
+    // Inline HTML is dropped, as on the rendered page.
+    let events = Parser::new_ext(
+        body,
+        Options::ENABLE_STRIKETHROUGH | Options::ENABLE_HEADING_ATTRIBUTES,
+    )
+    .filter_map(|event| {
+        Some(match event {
+            Event::Html(_) | Event::InlineHtml(_) => return None,
+            Event::Start(Tag::Link {
+                link_type,
+                dest_url,
+                title,
+                id,
+            }) => Event::Start(Tag::Link {
+                link_type,
+                dest_url: absolute(dest_url),
+                title,
+                id,
+            }),
+            Event::Start(Tag::Image {
+                link_type,
+                dest_url,
+                title,
+                id,
+            }) => Event::Start(Tag::Image {
+                link_type,
+                dest_url: absolute(dest_url),
+                title,
+                id,
+            }),
+            event => event,
+        })
+    });
+    let mut out = String::new();
+    html::push_html(&mut out, events);
+    Some(out)
+}
@@ -0,0 +1,215 @@
+//! RSS and Atom feeds of every published post, with the full post as content.
+//! They are built per request, so scheduled posts show up once they are out.
+
+use axum::http::header;
+use axum::response::{AppendHeaders, IntoResponse, Response};
+
+use super::{content, preview, Post, Visibility, POST_INDEX};
+use crate::site::{AUTHOR, SITE_NAME, SITE_ORIGIN};
+
+/// How long feed readers and proxies may cache a feed, in seconds.
+const MAX_AGE: u32 = 3600;
+
+/// `GET /blog/rss.xml`
+pub async fn rss() -> impl IntoResponse {
+    let visibility = preview::visibility();
+    respond(
+        "application/rss+xml; charset=utf-8",
+        rss_xml(&SITE_ORIGIN, &visibility),
+        &visibility,
+    )
+}
+
+/// `GET /blog/atom.xml`
+pub async fn atom() -> impl IntoResponse {
+    let visibility = preview::visibility();
+    respond(
+        "application/atom+xml; charset=utf-8",
+        atom_xml(&SITE_ORIGIN, &visibility),
+        &visibility,
+    )
+}
+
+fn respond(content_type: &'static str, body: String, visibility: &Visibility) -> Response {
+    // Without a published post there is nothing to date the feed by.
+    let last_modified = POST_INDEX.latest(visibility).map(|post| {
+        (
+            header::LAST_MODIFIED,
+            rfc_2822(post.date).replace("+0000", "GMT"),
+        )
+    });
+    (
+        [
+            (header::CONTENT_TYPE, content_type.to_string()),
+            (header::CACHE_CONTROL, format!("public, max-age={MAX_AGE}")),
+        ],
+        AppendHeaders(last_modified),
+        body,
+    )
+        .into_response()
+}
+
+fn rss_xml(origin: &str, visibility: &Visibility) -> String {
+    let mut xml = format!(
+        concat!(
+            "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n",
+            "<rss version=\"2.0\" xmlns:atom=\"http://www.w3.org/2005/Atom\">\n",
+            "<channel>\n",
+            "<title>{site}</title>\n",
+            "<link>{origin}/blog</link>\n",
+            "<description>Posts from {site}</description>\n",
+            "<atom:link href=\"{origin}/blog/rss.xml\" rel=\"self\" type=\"application/rss+xml\"/>\n",
+        ),
+        origin = escape(origin),
+        site = escape(SITE_NAME),
+    );
+    if let Some(latest) = POST_INDEX.latest(visibility) {
+        xml += &format!("<lastBuildDate>{}</lastBuildDate>\n", rfc_2822(latest.date));
+    }
+    for post in POST_INDEX.visible(visibility) {
+        let url = escape(&post_url(origin, post));
+        xml += &format!(
+            concat!(
+                "<item>\n",
+                "<title>{title}</title>\n",
+                "<link>{url}</link>\n",
+                "<guid isPermaLink=\"true\">{url}</guid>\n",
+                "<pubDate>{date}</pubDate>\n",
+                "{categories}",
+                "<description>{summary}</description>\n",
+                "<content:encoded xmlns:content=\"http://purl.org/rss/1.0/modules/content/\">{content}</content:encoded>\n",
+                "</item>\n",
+            ),
+            title = escape(post.title),
+            url = url,
+            date = rfc_2822(post.date),
+            categories = post
+                .tags
+                .iter()
+                .map(|tag| format!("<category>{}</category>\n", escape(tag)))
+                .collect::<String>(),
+            summary = escape(post.summary),
+            content = escape(&content::html(post.slug, origin).unwrap_or_default()),
+        );
+    }
+    xml + "</channel>\n</rss>\n"
+}
+
+fn atom_xml(origin: &str, visibility: &Visibility) -> String {
+    let updated = POST_INDEX
+        .latest(visibility)
+        .map(|post| rfc_3339(post.date))
+        .unwrap_or_default();
+    let mut xml = format!(
+        concat!(
+            "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n",
+            "<feed xmlns=\"http://www.w3.org/2005/Atom\">\n",
+            "<title>{site}</title>\n",
+            "<id>{origin}/blog</id>\n",
+            "<link href=\"{origin}/blog\"/>\n",
+            "<link href=\"{origin}/blog/atom.xml\" rel=\"self\"/>\n",
+            "<updated>{updated}</updated>\n",
+            "<author><name>{author}</name></author>\n",
+        ),
+        origin = escape(origin),
+        updated = updated,
+        author = escape(AUTHOR),
+        site = escape(SITE_NAME),
+    );
+    for post in POST_INDEX.visible(visibility) {
+        let url = escape(&post_url(origin, post));
+        xml += &format!(
+            concat!(
+                "<entry>\n",
+                "<title>{title}</title>\n",
+                "<id>{url}</id>\n",
+                "<link href=\"{url}\"/>\n",
+                "<published>{date}</published>\n",
+                "<updated>{date}</updated>\n",
+                "{categories}",
+                "<summary>{summary}</summary>\n",
+                "<content type=\"html\">{content}</content>\n",
+                "</entry>\n",
+            ),
+            title = escape(post.title),
+            url = url,
+            date = rfc_3339(post.date),
+            categories = post
+                .tags
+                .iter()
+                .map(|tag| format!("<category term=\"{}\"/>\n", escape(tag)))
+                .collect::<String>(),
+            summary = escape(post.summary),
+            content = escape(&content::html(post.slug, origin).unwrap_or_default()),
+        );
+    }
+    xml + "</feed>\n"
+}
+
+fn post_url(origin: &str, post: &Post) -> String {
+    format!("{origin}{}", post.path())
+}
+
+pub(super) fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// `2024-06-20` as `2024-06-20T00:00:00Z`.
+fn rfc_3339(date: &str) -> String {
+    format!("{date}T00:00:00Z")
+}
+
+/// `2024-06-20` as `Thu, 20 Jun 2024 00:00:00 +0000`. Post dates are checked
+/// by `build.rs`, so they always parse.
+fn rfc_2822(date: &str) -> String {
+    const DAYS: [&str; 7] = ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
+    const MONTHS: [&str; 12] = [
+        "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+    ];
+
+    let mut parts = date.splitn(3, '-').map(|part| part.parse().unwrap_or(1));
+    let (year, month, day): (usize, usize, usize) = (
+        parts.next().unwrap_or(1970),
+        parts.next().unwrap_or(1),
+        parts.next().unwrap_or(1),
+    );
+    // Sakamoto's method for the day of the week.
+    const OFFSETS: [usize; 12] = [0, 3, 2, 5, 0, 3, 5, 1, 4, 6, 2, 4];
+    let y = if month < 3 { year - 1 } else { year };
+    let weekday = (y + y / 4 - y / 100 + y / 400 + OFFSETS[month - 1] + day) % 7;
+    format!(
+        "{}, {day:02} {} {year} 00:00:00 +0000",
+        DAYS[weekday],
+        MONTHS[month - 1]
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rfc_3339_is_midnight_utc() {
+        assert_eq!(rfc_3339("2024-06-20"), "2024-06-20T00:00:00Z");
+    }
+
+    #[test]
+    fn rfc_2822_formats_known_dates() {
+        assert_eq!(rfc_2822("2024-06-20"), "Thu, 20 Jun 2024 00:00:00 +0000");
+        assert_eq!(rfc_2822("1970-01-01"), "Thu, 01 Jan 1970 00:00:00 +0000");
+        assert_eq!(rfc_2822("2000-12-31"), "Sun, 31 Dec 2000 00:00:00 +0000");
+    }
+
+    #[test]
+    fn rfc_2822_weekday_around_leap_days() {
+        // January and February count as months of the year before.
+        assert_eq!(rfc_2822("2024-02-29"), "Thu, 29 Feb 2024 00:00:00 +0000");
+        assert_eq!(rfc_2822("2024-03-01"), "Fri, 01 Mar 2024 00:00:00 +0000");
+        assert_eq!(rfc_2822("1900-03-01"), "Thu, 01 Mar 1900 00:00:00 +0000");
+        assert_eq!(rfc_2822("2023-01-01"), "Sun, 01 Jan 2023 00:00:00 +0000");
+    }
+}
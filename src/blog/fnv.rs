@@ -0,0 +1,10 @@
+//! FNV-1a, shared by `build.rs`, which names image variants by it, and the
+//! link preview cache.
+
+/// The 64-bit FNV-1a hash of `bytes`. Unlike `DefaultHasher` it is the same
+/// in every build, so it can name files that outlive one.
+pub fn fnv1a<'a>(bytes: impl IntoIterator<Item = &'a u8>) -> u64 {
+    bytes.into_iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
@@ -0,0 +1,601 @@
+//! A small syntax highlighter for the languages used in posts.
+//!
+//! Tokens get highlight.js class names, which `style/tailwind.css` colors.
+
+/// Kind of a highlighted token.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Highlight {
+    Keyword,
+    String,
+    Number,
+    Comment,
+    /// `true`, `false`, `null`.
+    Literal,
+    Type,
+    Function,
+    /// Macros and shell builtins.
+    BuiltIn,
+    /// Rust attributes.
+    Meta,
+    /// Object keys and settings.
+    Attribute,
+    Variable,
+    /// Rust lifetimes.
+    Symbol,
+}
+
+impl Highlight {
+    pub fn class(self) -> &'static str {
+        match self {
+            Highlight::Keyword => "hljs-keyword",
+            Highlight::String => "hljs-string",
+            Highlight::Number => "hljs-number",
+            Highlight::Comment => "hljs-comment",
+            Highlight::Literal => "hljs-literal",
+            Highlight::Type => "hljs-type",
+            Highlight::Function => "hljs-title function_",
+            Highlight::BuiltIn => "hljs-built_in",
+            Highlight::Meta => "hljs-meta",
+            Highlight::Attribute => "hljs-attr",
+            Highlight::Variable => "hljs-variable",
+            Highlight::Symbol => "hljs-symbol",
+        }
+    }
+}
+
+/// A run of source text, `None` for text that is not highlighted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Token<'a> {
+    pub highlight: Option<Highlight>,
+    pub text: &'a str,
+}
+
+/// Splits `code` into tokens. Unknown languages come back as one plain token.
+pub fn highlight<'a>(lang: &str, code: &'a str) -> Vec<Token<'a>> {
+    let mut scanner = Scanner::new(code);
+    match lang.to_ascii_lowercase().as_str() {
+        "rust" | "rs" => rust(&mut scanner),
+        "json" | "jsonc" => json(&mut scanner),
+        "bash" | "sh" | "shell" | "zsh" | "console" => bash(&mut scanner),
+        "ini" | "env" | "dotenv" | "properties" | "toml" => ini(&mut scanner),
+        _ => scanner.pos = code.len(),
+    }
+    scanner.finish()
+}
+
+const RUST_KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern",
+    "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref",
+    "return", "self", "Self", "static", "struct", "super", "trait", "type", "unsafe", "use",
+    "where", "while",
+];
+
+const RUST_PRIMITIVES: &[&str] = &[
+    "bool", "char", "f32", "f64", "i8", "i16", "i32", "i64", "i128", "isize", "str", "u8", "u16",
+    "u32", "u64", "u128", "usize",
+];
+
+const BASH_KEYWORDS: &[&str] = &[
+    "case", "do", "done", "elif", "else", "esac", "fi", "for", "function", "if", "in", "then",
+    "until", "while",
+];
+
+const BASH_BUILTINS: &[&str] = &[
+    "alias", "cd", "echo", "eval", "exec", "exit", "export", "read", "set", "source", "sudo",
+    "unset",
+];
+
+fn rust(s: &mut Scanner) {
+    // Whether the last word was `fn`, naming a function even if generic.
+    let mut after_fn = false;
+    while let Some(c) = s.peek() {
+        let start = s.pos;
+        if s.at("//") {
+            s.line();
+            s.emit(start, Highlight::Comment);
+        } else if s.at("/*") {
+            // Block comments nest in Rust.
+            let mut depth = 0;
+            while s.peek().is_some() {
+                if s.at("/*") {
+                    depth += 1;
+                    s.pos += 2;
+                } else if s.at("*/") {
+                    depth -= 1;
+                    s.pos += 2;
+                    if depth == 0 {
+                        break;
+                    }
+                } else {
+                    s.bump();
+                }
+            }
+            s.emit(start, Highlight::Comment);
+        } else if s.at("#[") || s.at("#![") {
+            s.balanced('[', ']');
+            s.emit(start, Highlight::Meta);
+        } else if s.at("r#\"") || s.at("r\"") {
+            s.bump();
+            let hashes = s.take_while(|c| c == '#').len();
+            s.bump();
+            s.until_after(&format!("\"{}", "#".repeat(hashes)));
+            s.emit(start, Highlight::String);
+        } else if s.at("b\"") || c == '"' {
+            if c == 'b' {
+                s.bump();
+            }
+            s.quoted('"');
+            s.emit(start, Highlight::String);
+        } else if c == '\'' {
+            // `'a'` and `'\n'` are chars, `'a` on its own is a lifetime.
+            let rest = &s.rest()[1..];
+            let char_len = if rest.starts_with('\\') {
+                rest.get(2..)
+                    .and_then(|rest| rest.find('\''))
+                    .map(|end| end + 3)
+            } else {
+                rest.chars()
+                    .next()
+                    .filter(|c| rest[c.len_utf8()..].starts_with('\''))
+                    .map(|c| c.len_utf8() + 1)
+            };
+            match char_len {
+                Some(len) => {
+                    s.pos += 1 + len;
+                    s.emit(start, Highlight::String);
+                }
+                None => {
+                    s.bump();
+                    s.take_while(is_ident);
+                    s.emit(start, Highlight::Symbol);
+                }
+            }
+        } else if c.is_ascii_digit() {
+            s.take_while(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.');
+            s.emit(start, Highlight::Number);
+        } else if is_ident_start(c) {
+            let word = s.take_while(is_ident);
+            let named = std::mem::replace(&mut after_fn, word == "fn");
+            if RUST_KEYWORDS.contains(&word) {
+                s.emit(start, Highlight::Keyword);
+            } else if word == "true" || word == "false" {
+                s.emit(start, Highlight::Literal);
+            } else if s.at("!") && !s.at("!=") {
+                s.bump();
+                s.emit(start, Highlight::BuiltIn);
+            } else if RUST_PRIMITIVES.contains(&word) || word.starts_with(char::is_uppercase) {
+                s.emit(start, Highlight::Type);
+            } else if named || s.at("(") || s.at("::<") {
+                s.emit(start, Highlight::Function);
+            }
+        } else {
+            s.bump();
+        }
+    }
+}
+
+fn json(s: &mut Scanner) {
+    while let Some(c) = s.peek() {
+        let start = s.pos;
+        if c == '"' {
+            s.quoted('"');
+            let is_key = s.rest().trim_start().starts_with(':');
+            s.emit(
+                start,
+                if is_key {
+                    Highlight::Attribute
+                } else {
+                    Highlight::String
+                },
+            );
+        } else if c == '-' || c.is_ascii_digit() {
+            s.take_while(|c| c.is_ascii_digit() || "-+.eE".contains(c));
+            s.emit(start, Highlight::Number);
+        } else if c.is_ascii_alphabetic() {
+            let word = s.take_while(|c| c.is_ascii_alphabetic());
+            if matches!(word, "true" | "false" | "null") {
+                s.emit(start, Highlight::Literal);
+            }
+        } else if s.at("//") {
+            s.line();
+            s.emit(start, Highlight::Comment);
+        } else {
+            s.bump();
+        }
+    }
+}
+
+fn bash(s: &mut Scanner) {
+    // Whether the next word is in command position.
+    let mut command = true;
+    while let Some(c) = s.peek() {
+        let start = s.pos;
+        if c == '#' && (start == 0 || s.code[..start].ends_with(char::is_whitespace)) {
+            s.line();
+            s.emit(start, Highlight::Comment);
+        } else if c == '\'' {
+            // Nothing is escaped in single quotes.
+            s.bump();
+            s.until_after("'");
+            s.emit(start, Highlight::String);
+            command = false;
+        } else if c == '"' {
+            s.quoted('"');
+            s.emit(start, Highlight::String);
+            command = false;
+        } else if c == '$' {
+            s.bump();
+            if s.at("{") {
+                s.balanced('{', '}');
+            } else {
+                s.take_while(is_ident);
+            }
+            s.emit(start, Highlight::Variable);
+            command = false;
+        } else if c == '\n' || c == ';' || c == '|' || c == '&' {
+            s.bump();
+            command = true;
+        } else if c.is_whitespace() {
+            s.bump();
+        } else {
+            let word = s.take_while(|c| !c.is_whitespace() && !";|&'\"$".contains(c));
+            if word.is_empty() {
+                s.bump();
+            } else if BASH_KEYWORDS.contains(&word) {
+                s.emit(start, Highlight::Keyword);
+                command = true;
+                continue;
+            } else if command && BASH_BUILTINS.contains(&word) {
+                s.emit(start, Highlight::BuiltIn);
+            } else if let Some((name, _)) = word
+                .split_once('=')
+                .filter(|(name, _)| command && !name.is_empty() && name.chars().all(is_ident))
+            {
+                // `NAME=value` before a command, which still follows.
+                s.pos = start + name.len();
+                s.emit(start, Highlight::Variable);
+                s.pos = start + word.len();
+                continue;
+            } else if word.parse::<f64>().is_ok() {
+                s.emit(start, Highlight::Number);
+            }
+            command = false;
+        }
+    }
+}
+
+/// `KEY=value` files such as `.env`, plus `[sections]` and `key = value` for
+/// ini and simple TOML.
+fn ini(s: &mut Scanner) {
+    while s.peek().is_some() {
+        let line = s.rest().split('\n').next().unwrap_or_default();
+        let trimmed = line.trim_start();
+        s.pos += line.len() - trimmed.len();
+        let start_of_text = s.pos;
+
+        if trimmed.starts_with('#') || trimmed.starts_with(';') {
+            s.line();
+            s.emit(start_of_text, Highlight::Comment);
+        } else if trimmed.starts_with('[') {
+            s.line();
+            s.emit(start_of_text, Highlight::Type);
+        } else if let Some(eq) = trimmed.find('=') {
+            let key = trimmed[..eq].trim_end();
+            s.pos = start_of_text + key.len();
+            s.emit(start_of_text, Highlight::Attribute);
+            s.pos = start_of_text + eq + 1;
+            s.take_while(|c| c == ' ' || c == '\t');
+            let value_start = s.pos;
+            let value = s.line();
+            let highlight = match value.trim() {
+                "true" | "false" => Highlight::Literal,
+                value if value.parse::<f64>().is_ok() => Highlight::Number,
+                _ => Highlight::String,
+            };
+            if !value.is_empty() {
+                s.emit(value_start, highlight);
+            }
+        } else {
+            s.line();
+        }
+        s.bump();
+    }
+}
+
+fn is_ident_start(c: char) -> bool {
+    c.is_alphabetic() || c == '_'
+}
+
+fn is_ident(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+struct Scanner<'a> {
+    code: &'a str,
+    pos: usize,
+    /// Start of the text not yet emitted.
+    plain: usize,
+    tokens: Vec<Token<'a>>,
+}
+
+impl<'a> Scanner<'a> {
+    fn new(code: &'a str) -> Self {
+        Scanner {
+            code,
+            pos: 0,
+            plain: 0,
+            tokens: Vec::new(),
+        }
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.code[self.pos..]
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn at(&self, prefix: &str) -> bool {
+        self.rest().starts_with(prefix)
+    }
+
+    fn bump(&mut self) {
+        if let Some(c) = self.peek() {
+            self.pos += c.len_utf8();
+        }
+    }
+
+    fn take_while(&mut self, mut accept: impl FnMut(char) -> bool) -> &'a str {
+        let start = self.pos;
+        let len = self
+            .rest()
+            .find(|c| !accept(c))
+            .unwrap_or(self.rest().len());
+        self.pos += len;
+        &self.code[start..self.pos]
+    }
+
+    /// Up to, not including, the end of the line.
+    fn line(&mut self) -> &'a str {
+        self.take_while(|c| c != '\n')
+    }
+
+    fn until_after(&mut self, end: &str) {
+        self.pos = match self.rest().find(end) {
+            Some(i) => self.pos + i + end.len(),
+            None => self.code.len(),
+        };
+    }
+
+    /// A string opened by the current `quote`, with backslash escapes.
+    fn quoted(&mut self, quote: char) {
+        self.bump();
+        while let Some(c) = self.peek() {
+            self.bump();
+            if c == '\\' {
+                self.bump();
+            } else if c == quote {
+                break;
+            }
+        }
+    }
+
+    /// From the current position through the `close` matching the first `open`.
+    fn balanced(&mut self, open: char, close: char) {
+        let mut depth = 0;
+        while let Some(c) = self.peek() {
+            self.bump();
+            if c == open {
+                depth += 1;
+            } else if c == close {
+                depth -= 1;
+                if depth == 0 {
+                    break;
+                }
+            }
+        }
+    }
+
+    /// Marks `start..pos` as `highlight`, keeping the text before it plain.
+    fn emit(&mut self, start: usize, highlight: Highlight) {
+        self.flush(start);
+        self.tokens.push(Token {
+            highlight: Some(highlight),
+            text: &self.code[start..self.pos],
+        });
+        self.plain = self.pos;
+    }
+
+    fn flush(&mut self, end: usize) {
+        if end > self.plain {
+            self.tokens.push(Token {
+                highlight: None,
+                text: &self.code[self.plain..end],
+            });
+        }
+    }
+
+    fn finish(mut self) -> Vec<Token<'a>> {
+        self.flush(self.code.len());
+        self.tokens
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Highlight::*;
+    use super::*;
+
+    /// The highlighted tokens of `code`, after checking nothing got lost.
+    fn marked<'a>(lang: &str, code: &'a str) -> Vec<(Highlight, &'a str)> {
+        let tokens = highlight(lang, code);
+        assert_eq!(
+            tokens
+                .iter()
+                .map(|token| token.text)
+                .collect::<std::string::String>(),
+            code
+        );
+        tokens
+            .into_iter()
+            .filter_map(|token| Some((token.highlight?, token.text)))
+            .collect()
+    }
+
+    #[test]
+    fn rust_comments_nest() {
+        assert_eq!(
+            marked("rust", "/* a /* b */ c */ x // d\ny"),
+            [(Comment, "/* a /* b */ c */"), (Comment, "// d")]
+        );
+        // An unclosed comment runs to the end.
+        assert_eq!(marked("rs", "/* a /* b */"), [(Comment, "/* a /* b */")]);
+    }
+
+    #[test]
+    fn rust_strings() {
+        assert_eq!(
+            marked("rust", r#"let s = "a \"b\" c";"#),
+            [(Keyword, "let"), (String, r#""a \"b\" c""#)]
+        );
+        assert_eq!(
+            marked("rust", r##"r#"say "hi""# + r"\d" + b"x""##),
+            [
+                (String, r##"r#"say "hi""#"##),
+                (String, r#"r"\d""#),
+                (String, r#"b"x""#)
+            ]
+        );
+    }
+
+    #[test]
+    fn rust_lifetimes_and_chars() {
+        assert_eq!(
+            marked("rust", r"fn f<'a>(s: &'a str) -> char { 'a' }"),
+            [
+                (Keyword, "fn"),
+                (Function, "f"),
+                (Symbol, "'a"),
+                (Symbol, "'a"),
+                (Type, "str"),
+                (Type, "char"),
+                (String, "'a'"),
+            ]
+        );
+        assert_eq!(
+            marked("rust", r"['\'', '\n', '\u{1F980}', 'é', &'static]"),
+            [
+                (String, r"'\''"),
+                (String, r"'\n'"),
+                (String, r"'\u{1F980}'"),
+                (String, "'é'"),
+                (Symbol, "'static"),
+            ]
+        );
+    }
+
+    #[test]
+    fn rust_items() {
+        assert_eq!(
+            marked(
+                "rust",
+                "#[derive(Debug)]\nprintln!(\"{}\", Vec::<u8>::new() != x, 1.5, true);"
+            ),
+            [
+                (Meta, "#[derive(Debug)]"),
+                (BuiltIn, "println!"),
+                (String, "\"{}\""),
+                (Type, "Vec"),
+                (Type, "u8"),
+                (Function, "new"),
+                (Number, "1.5"),
+                (Literal, "true"),
+            ]
+        );
+    }
+
+    #[test]
+    fn json_keys_and_values() {
+        assert_eq!(
+            marked(
+                "json",
+                r#"{"a\"b" : "c", "n": -1.5e3, "ok": [true, null]} // x"#
+            ),
+            [
+                (Attribute, r#""a\"b""#),
+                (String, r#""c""#),
+                (Attribute, r#""n""#),
+                (Number, "-1.5e3"),
+                (Attribute, r#""ok""#),
+                (Literal, "true"),
+                (Literal, "null"),
+                (Comment, "// x"),
+            ]
+        );
+    }
+
+    #[test]
+    fn bash_commands_and_quotes() {
+        assert_eq!(
+            marked(
+                "bash",
+                "# setup\nFOO=1 echo \"a \\\"$b\\\"\" 'c\\' ${D} 2 # e\nif cd x; then exit; fi"
+            ),
+            [
+                (Comment, "# setup"),
+                (Variable, "FOO"),
+                (BuiltIn, "echo"),
+                (String, "\"a \\\"$b\\\"\""),
+                (String, "'c\\'"),
+                (Variable, "${D}"),
+                (Number, "2"),
+                (Comment, "# e"),
+                (Keyword, "if"),
+                (BuiltIn, "cd"),
+                (Keyword, "then"),
+                (BuiltIn, "exit"),
+                (Keyword, "fi"),
+            ]
+        );
+        // `#` inside a word starts no comment, builtins only count as commands.
+        assert_eq!(marked("sh", "git log a#b | echo cd"), [(BuiltIn, "echo")]);
+    }
+
+    #[test]
+    fn ini_and_env() {
+        assert_eq!(
+            marked(
+                "toml",
+                "# c\n[server]\nport = 3000\nhost = \"0.0.0.0\"\n  debug=true\n; d"
+            ),
+            [
+                (Comment, "# c"),
+                (Type, "[server]"),
+                (Attribute, "port"),
+                (Number, "3000"),
+                (Attribute, "host"),
+                (String, "\"0.0.0.0\""),
+                (Attribute, "debug"),
+                (Literal, "true"),
+                (Comment, "; d"),
+            ]
+        );
+        assert_eq!(
+            marked("env", "KEY=a=b\nEMPTY="),
+            [(Attribute, "KEY"), (String, "a=b"), (Attribute, "EMPTY")]
+        );
+    }
+
+    #[test]
+    fn unknown_languages_stay_plain() {
+        assert_eq!(
+            highlight("Brainfuck", "+[>]"),
+            [Token {
+                highlight: None,
+                text: "+[>]"
+            }]
+        );
+        assert_eq!(marked("RUST", "fn")[0], (Keyword, "fn"));
+    }
+}
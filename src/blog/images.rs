@@ -0,0 +1,69 @@
+//! Resized AVIF and WebP variants of the images under `public/blog`, which
+//! `build.rs` makes and the server embeds.
+
+// Defines `IMAGE_INDEX`, see `build/images.rs`.
+include!(concat!(env!("OUT_DIR"), "/image_index.rs"));
+
+/// An image under `public/blog` and its variants.
+pub struct ResponsiveImage {
+    /// Path of the original, e.g. `/blog/esp32-relay/hardware.jpg`.
+    pub src: &'static str,
+    pub width: u32,
+    pub height: u32,
+    /// Path of the variants without the `-<width>.<format>` suffix.
+    variants: &'static str,
+    /// Widths of the variants, ascending.
+    widths: &'static [u32],
+    /// A tiny WebP data URL to show while the image loads.
+    pub placeholder: &'static str,
+}
+
+impl ResponsiveImage {
+    /// The image at `src`, if it is one `build.rs` made variants of.
+    pub fn get(src: &str) -> Option<&'static ResponsiveImage> {
+        IMAGE_INDEX.iter().find(|image| image.src == src)
+    }
+
+    /// A `srcset` listing every variant in `format`, `avif` or `webp`.
+    pub fn srcset(&self, format: &str) -> String {
+        self.widths
+            .iter()
+            .map(|width| format!("{}-{width}.{format} {width}w", self.variants))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+#[cfg(feature = "ssr")]
+mod serve {
+    use axum::extract::Path;
+    use axum::http::{header, StatusCode};
+    use axum::response::{IntoResponse, Response};
+
+    // Defines `IMAGE_FILES`, the file name and bytes of every variant.
+    include!(concat!(env!("OUT_DIR"), "/image_files.rs"));
+
+    /// `GET /blog/img/{file}`. Variant names change with their content, so
+    /// they can be cached for good.
+    pub async fn variant(Path(file): Path<String>) -> Response {
+        let Some((_, bytes)) = IMAGE_FILES.iter().find(|(name, _)| *name == file) else {
+            return StatusCode::NOT_FOUND.into_response();
+        };
+        let content_type = if file.ends_with(".avif") {
+            "image/avif"
+        } else {
+            "image/webp"
+        };
+        (
+            [
+                (header::CONTENT_TYPE, content_type),
+                (header::CACHE_CONTROL, "public, max-age=31536000, immutable"),
+            ],
+            *bytes,
+        )
+            .into_response()
+    }
+}
+
+#[cfg(feature = "ssr")]
+pub use serve::variant;
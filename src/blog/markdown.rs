@@ -0,0 +1,434 @@
+use serde::{Deserialize, Serialize};
+
+/// A block of a rendered post. Posts are parsed on the server and sent to the
+/// client in this form, so the Markdown parser never ends up in the WASM bundle.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Block {
+    /// `id` is unique within the post, so it can be linked to as `#id`.
+    Heading {
+        level: u8,
+        id: String,
+        children: Vec<Inline>,
+    },
+    Paragraph(Vec<Inline>),
+    /// A fenced or indented code block, `lang` is the first word of the fence.
+    Code {
+        lang: Option<String>,
+        code: String,
+        options: CodeOptions,
+    },
+    /// `start` is the first number of an ordered list.
+    List {
+        start: Option<u64>,
+        items: Vec<Vec<Block>>,
+    },
+    Quote(Vec<Block>),
+    Rule,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Inline {
+    Text(String),
+    Code(String),
+    Emphasis(Vec<Inline>),
+    Strong(Vec<Inline>),
+    Strikethrough(Vec<Inline>),
+    Link { href: String, children: Vec<Inline> },
+    Image { src: String, alt: String },
+    LineBreak,
+}
+
+/// Extras set after the language in a fence, e.g.
+/// ```` ```rust title="src/main.rs" numbers {3-5,8} ````.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CodeOptions {
+    /// Shown above the code instead of the language, usually a file name.
+    pub title: Option<String>,
+    pub line_numbers: bool,
+    /// Inclusive, 1-based ranges of lines to mark.
+    pub highlighted: Vec<(usize, usize)>,
+}
+
+/// A heading listed in a post's table of contents.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TocEntry {
+    pub level: u8,
+    pub id: String,
+    pub title: String,
+}
+
+/// The `##` and `###` headings of a post, in order.
+pub fn toc(blocks: &[Block]) -> Vec<TocEntry> {
+    blocks
+        .iter()
+        .filter_map(|block| match block {
+            Block::Heading {
+                level,
+                id,
+                children,
+            } if (2..=3).contains(level) => Some(TocEntry {
+                level: *level,
+                id: id.clone(),
+                title: plain_text(children),
+            }),
+            _ => None,
+        })
+        .collect()
+}
+
+/// The text of `inlines` without formatting.
+pub fn plain_text(inlines: &[Inline]) -> String {
+    inlines
+        .iter()
+        .map(|inline| match inline {
+            Inline::Text(text) | Inline::Code(text) => text.clone(),
+            Inline::Emphasis(children)
+            | Inline::Strong(children)
+            | Inline::Strikethrough(children)
+            | Inline::Link { children, .. } => plain_text(children),
+            Inline::Image { alt, .. } => alt.clone(),
+            Inline::LineBreak => " ".to_string(),
+        })
+        .collect()
+}
+
+#[cfg(feature = "ssr")]
+pub use parse::parse;
+
+#[cfg(feature = "ssr")]
+mod parse {
+    use std::collections::HashSet;
+    use std::iter::Peekable;
+
+    use pulldown_cmark::{CodeBlockKind, Event, Options, Parser, Tag};
+
+    use super::{plain_text, Block, CodeOptions, Inline};
+
+    type Events<'a> = Peekable<Parser<'a>>;
+
+    pub fn parse(markdown: &str) -> Vec<Block> {
+        let options = Options::ENABLE_STRIKETHROUGH | Options::ENABLE_HEADING_ATTRIBUTES;
+        let mut events = Parser::new_ext(markdown, options).peekable();
+        let mut parsed = blocks(&mut events);
+        heading_ids(&mut parsed, &mut HashSet::new());
+        parsed
+    }
+
+    /// Gives every heading without an `{#id}` one made from its text, adding
+    /// `-1`, `-2`, ... where headings share a text.
+    fn heading_ids(blocks: &mut [Block], used: &mut HashSet<String>) {
+        for block in blocks {
+            match block {
+                Block::Heading { id, children, .. } => {
+                    let base = if id.is_empty() {
+                        slugify(&plain_text(children))
+                    } else {
+                        id.clone()
+                    };
+                    let mut unique = base.clone();
+                    let mut n = 0;
+                    while !used.insert(unique.clone()) {
+                        n += 1;
+                        unique = format!("{base}-{n}");
+                    }
+                    *id = unique;
+                }
+                Block::Quote(blocks) => heading_ids(blocks, used),
+                Block::List { items, .. } => {
+                    for item in items {
+                        heading_ids(item, used);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// `AWS IoT setup` as `aws-iot-setup`.
+    fn slugify(text: &str) -> String {
+        let slug = text
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .map(str::to_lowercase)
+            .collect::<Vec<_>>()
+            .join("-");
+        if slug.is_empty() {
+            "section".to_string()
+        } else {
+            slug
+        }
+    }
+
+    /// Reads blocks up to the end of the enclosing tag.
+    fn blocks(events: &mut Events) -> Vec<Block> {
+        let mut parsed = Vec::new();
+        while let Some(event) = events.next() {
+            let block = match event {
+                Event::End(_) => break,
+                Event::Rule => Block::Rule,
+                Event::Start(Tag::Paragraph) => Block::Paragraph(inlines(events)),
+                Event::Start(Tag::Heading { level, id, .. }) => Block::Heading {
+                    level: level as u8,
+                    id: id.map(|id| id.into_string()).unwrap_or_default(),
+                    children: inlines(events),
+                },
+                Event::Start(Tag::BlockQuote(_)) => Block::Quote(blocks(events)),
+                Event::Start(Tag::CodeBlock(kind)) => {
+                    let (lang, options) = match kind {
+                        CodeBlockKind::Fenced(info) => fence(&info),
+                        CodeBlockKind::Indented => (None, CodeOptions::default()),
+                    };
+                    let mut code = String::new();
+                    for event in events.by_ref() {
+                        match event {
+                            Event::Text(text) => code.push_str(&text),
+                            _ => break,
+                        }
+                    }
+                    Block::Code {
+                        lang,
+                        code: code.trim_end().to_string(),
+                        options,
+                    }
+                }
+                Event::Start(Tag::List(start)) => {
+                    let mut items = Vec::new();
+                    while let Some(Event::Start(Tag::Item)) = events.next() {
+                        items.push(blocks(events));
+                    }
+                    Block::List { start, items }
+                }
+                Event::Start(Tag::HtmlBlock) => {
+                    // Raw HTML is not rendered.
+                    skip(events);
+                    continue;
+                }
+                event => {
+                    // Items of a tight list hold their text without a paragraph.
+                    let mut children: Vec<Inline> = inline(event, events).into_iter().collect();
+                    while events.peek().is_some_and(is_inline) {
+                        let event = events.next().expect("peeked");
+                        children.extend(inline(event, events));
+                    }
+                    if children.is_empty() {
+                        continue;
+                    }
+                    Block::Paragraph(children)
+                }
+            };
+            parsed.push(block);
+        }
+        parsed
+    }
+
+    /// Reads a fence's info string: the language, then `title="..."`, `numbers`
+    /// and `{1,3-5}` in any order. Anything else, including ranges that are
+    /// empty or start at 0, is ignored.
+    fn fence(info: &str) -> (Option<String>, CodeOptions) {
+        let mut words = Vec::new();
+        let mut word = String::new();
+        let mut quoted = false;
+        for c in info.chars() {
+            match c {
+                '"' => quoted = !quoted,
+                c if c.is_whitespace() && !quoted => {
+                    words.push(std::mem::take(&mut word));
+                }
+                c => word.push(c),
+            }
+        }
+        words.push(word);
+        words.retain(|word| !word.is_empty());
+
+        let mut words = words.into_iter();
+        let lang = words.next();
+        let mut options = CodeOptions::default();
+        for word in words {
+            if let Some(title) = word.strip_prefix("title=") {
+                options.title = Some(title.to_string());
+            } else if word == "numbers" {
+                options.line_numbers = true;
+            } else if let Some(ranges) = word.strip_prefix('{').and_then(|w| w.strip_suffix('}')) {
+                options.highlighted = ranges
+                    .split(',')
+                    .filter_map(|range| {
+                        let (start, end) = range.split_once('-').unwrap_or((range, range));
+                        let start = start.trim().parse().ok()?;
+                        let end = end.trim().parse().ok()?;
+                        (1 <= start && start <= end).then_some((start, end))
+                    })
+                    .collect();
+            }
+        }
+        (lang, options)
+    }
+
+    /// Reads inline content up to the end of the enclosing tag.
+    fn inlines(events: &mut Events) -> Vec<Inline> {
+        let mut children = Vec::new();
+        while let Some(event) = events.next() {
+            if let Event::End(_) = event {
+                break;
+            }
+            children.extend(inline(event, events));
+        }
+        children
+    }
+
+    fn inline(event: Event, events: &mut Events) -> Option<Inline> {
+        Some(match event {
+            Event::Text(text) => Inline::Text(text.into_string()),
+            Event::Code(code) => Inline::Code(code.into_string()),
+            Event::SoftBreak => Inline::Text(" ".to_string()),
+            Event::HardBreak => Inline::LineBreak,
+            Event::Start(Tag::Emphasis) => Inline::Emphasis(inlines(events)),
+            Event::Start(Tag::Strong) => Inline::Strong(inlines(events)),
+            Event::Start(Tag::Strikethrough) => Inline::Strikethrough(inlines(events)),
+            Event::Start(Tag::Link { dest_url, .. }) => Inline::Link {
+                href: dest_url.into_string(),
+                children: inlines(events),
+            },
+            Event::Start(Tag::Image { dest_url, .. }) => Inline::Image {
+                src: dest_url.into_string(),
+                alt: plain_text(&inlines(events)),
+            },
+            Event::Start(_) => {
+                skip(events);
+                return None;
+            }
+            _ => return None,
+        })
+    }
+
+    fn is_inline(event: &Event) -> bool {
+        match event {
+            Event::Text(_) | Event::Code(_) | Event::SoftBreak | Event::HardBreak => true,
+            Event::Start(tag) => matches!(
+                tag,
+                Tag::Emphasis
+                    | Tag::Strong
+                    | Tag::Strikethrough
+                    | Tag::Link { .. }
+                    | Tag::Image { .. }
+            ),
+            _ => false,
+        }
+    }
+
+    /// Skips to the end of the tag that was just started.
+    fn skip(events: &mut Events) {
+        let mut depth = 1;
+        for event in events.by_ref() {
+            match event {
+                Event::Start(_) => depth += 1,
+                Event::End(_) => depth -= 1,
+                _ => {}
+            }
+            if depth == 0 {
+                break;
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::super::toc;
+        use super::*;
+
+        fn text(text: &str) -> Inline {
+            Inline::Text(text.to_string())
+        }
+
+        fn paragraph(content: &str) -> Block {
+            Block::Paragraph(vec![text(content)])
+        }
+
+        #[test]
+        fn tight_list_items_hold_paragraphs() {
+            assert_eq!(
+                parse("- one\n- *two*\n"),
+                [Block::List {
+                    start: None,
+                    items: vec![
+                        vec![paragraph("one")],
+                        vec![Block::Paragraph(vec![Inline::Emphasis(vec![text("two")])])],
+                    ],
+                }]
+            );
+        }
+
+        #[test]
+        fn loose_list_items_hold_paragraphs() {
+            assert_eq!(
+                parse("3. one\n\n   more\n\n4. two\n"),
+                [Block::List {
+                    start: Some(3),
+                    items: vec![
+                        vec![paragraph("one"), paragraph("more")],
+                        vec![paragraph("two")],
+                    ],
+                }]
+            );
+        }
+
+        #[test]
+        fn drops_raw_html() {
+            let blocks =
+                parse("<div>\n<script>alert(1)</script>\n</div>\n\nan <b>inline</b> tag\n");
+            assert_eq!(blocks.len(), 1);
+            let Block::Paragraph(children) = &blocks[0] else {
+                panic!("{blocks:?}");
+            };
+            assert_eq!(plain_text(children), "an inline tag");
+        }
+
+        #[test]
+        fn reads_fence_options() {
+            assert_eq!(
+                fence(r#"rust title="src/main file.rs" numbers {1,3-5}"#),
+                (
+                    Some("rust".to_string()),
+                    CodeOptions {
+                        title: Some("src/main file.rs".to_string()),
+                        line_numbers: true,
+                        highlighted: vec![(1, 1), (3, 5)],
+                    }
+                )
+            );
+            assert_eq!(fence(""), (None, CodeOptions::default()));
+            assert_eq!(
+                fence("sh extra"),
+                (Some("sh".to_string()), CodeOptions::default())
+            );
+        }
+
+        #[test]
+        fn skips_bad_ranges() {
+            let (_, options) = fence("rust {0,a-2,5-3,2-,-4,7,8-9}");
+            assert_eq!(options.highlighted, [(7, 7), (8, 9)]);
+            let (_, options) = fence("rust {2-4,}");
+            assert_eq!(options.highlighted, [(2, 4)]);
+        }
+
+        #[test]
+        fn numbers_duplicate_headings() {
+            let ids: Vec<_> = toc(&parse(
+                "## Setup\n\n## Setup\n\n### Setup\n\n## Other {#setup-1x}\n",
+            ))
+            .into_iter()
+            .map(|entry| entry.id)
+            .collect();
+            assert_eq!(ids, ["setup", "setup-1", "setup-2", "setup-1x"]);
+        }
+
+        #[test]
+        fn slugifies() {
+            assert_eq!(slugify("AWS IoT setup"), "aws-iot-setup");
+            assert_eq!(slugify("What's new? (v2.0)"), "what-s-new-v2-0");
+            assert_eq!(slugify("  --Rust & C++--  "), "rust-c");
+            assert_eq!(slugify("Ölkännchen für Ärzte"), "ölkännchen-für-ärzte");
+            assert_eq!(slugify("日本語 テスト"), "日本語-テスト");
+            assert_eq!(slugify("?!"), "section");
+        }
+    }
+}
@@ -0,0 +1,374 @@
+//! Blog posts, written as Markdown files under `content/blog`.
+//!
+//! `build.rs` validates every post and generates [`POST_INDEX`], so a broken
+//! post fails the build instead of 404ing. On the server drafts and scheduled
+//! posts are in the index too, a [`Visibility`] decides who gets to see them.
+//! The browser's index leaves them out, [`get_visibility`] sends the ones a
+//! visitor may see.
+
+use std::collections::BTreeMap;
+
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+
+pub mod highlight;
+pub mod images;
+pub mod markdown;
+pub mod search;
+
+#[cfg(feature = "ssr")]
+mod content;
+#[cfg(feature = "ssr")]
+pub mod feed;
+#[cfg(feature = "ssr")]
+mod fnv;
+#[cfg(feature = "ssr")]
+pub mod og_image;
+#[cfg(feature = "ssr")]
+mod preview;
+#[cfg(feature = "ssr")]
+pub mod sitemap;
+#[cfg(feature = "ssr")]
+mod words;
+
+use markdown::Block;
+
+/// A post's front matter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Post {
+    pub slug: &'static str,
+    pub title: &'static str,
+    /// `YYYY-MM-DD`.
+    pub date: &'static str,
+    pub tags: &'static [&'static str],
+    pub summary: &'static str,
+    pub draft: bool,
+    /// `YYYY-MM-DD` before which the post is hidden.
+    pub publish_at: Option<&'static str>,
+}
+
+impl Post {
+    /// Where the post is served.
+    pub fn path(&self) -> String {
+        format!("/blog/{}", self.slug)
+    }
+
+    /// Where the post's link preview image is served, see `og_image`.
+    pub fn og_image_path(&self) -> String {
+        format!("/blog/og/{}.png", self.slug)
+    }
+
+    /// The date as shown to readers, e.g. `June 20, 2024`.
+    pub fn display_date(&self) -> String {
+        display_date(self.date)
+    }
+
+    /// Whether the post is out on `today`, a `YYYY-MM-DD` date.
+    pub fn is_published(&self, today: &str) -> bool {
+        !self.draft && self.publish_at.is_none_or(|at| at <= today)
+    }
+
+    /// Whether the post is out no matter the date, which is what the browser's
+    /// index holds. Keep in sync with `build.rs`.
+    #[cfg(feature = "ssr")]
+    pub fn is_always_public(&self) -> bool {
+        !self.draft && self.publish_at.is_none()
+    }
+}
+
+/// A [`Post`] as sent to the browser.
+#[derive(Serialize, Deserialize)]
+struct SentPost {
+    slug: String,
+    title: String,
+    date: String,
+    tags: Vec<String>,
+    summary: String,
+    draft: bool,
+    publish_at: Option<String>,
+}
+
+impl SentPost {
+    fn new(post: &Post) -> Self {
+        SentPost {
+            slug: post.slug.to_string(),
+            title: post.title.to_string(),
+            date: post.date.to_string(),
+            tags: post.tags.iter().map(|tag| tag.to_string()).collect(),
+            summary: post.summary.to_string(),
+            draft: post.draft,
+            publish_at: post.publish_at.map(str::to_string),
+        }
+    }
+
+    /// Leaks the post to live as long as the ones in the index. The browser
+    /// receives them once per page load.
+    fn leak(self) -> &'static Post {
+        fn leak(text: String) -> &'static str {
+            Box::leak(text.into_boxed_str())
+        }
+        let tags: Vec<&'static str> = self.tags.into_iter().map(leak).collect();
+        Box::leak(Box::new(Post {
+            slug: leak(self.slug),
+            title: leak(self.title),
+            date: leak(self.date),
+            tags: Box::leak(tags.into_boxed_slice()),
+            summary: leak(self.summary),
+            draft: self.draft,
+            publish_at: self.publish_at.map(leak),
+        }))
+    }
+}
+
+fn serialize_posts<S: serde::Serializer>(
+    posts: &[&'static Post],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_seq(posts.iter().map(|post| SentPost::new(post)))
+}
+
+fn deserialize_posts<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Vec<&'static Post>, D::Error> {
+    let posts = Vec::<SentPost>::deserialize(deserializer)?;
+    Ok(posts.into_iter().map(SentPost::leak).collect())
+}
+
+/// Which posts a visitor may see.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Visibility {
+    /// `YYYY-MM-DD`, in UTC.
+    pub today: String,
+    /// Shows drafts and scheduled posts, in DEV or with the preview token.
+    pub preview: bool,
+    /// Posts the browser's index leaves out that the visitor may see, set by
+    /// [`get_visibility`].
+    #[serde(
+        default,
+        serialize_with = "serialize_posts",
+        deserialize_with = "deserialize_posts"
+    )]
+    posts: Vec<&'static Post>,
+}
+
+impl Visibility {
+    /// Published posts as of now, what every visitor sees.
+    pub fn public() -> Self {
+        Visibility {
+            today: today(),
+            preview: false,
+            posts: Vec::new(),
+        }
+    }
+
+    pub fn allows(&self, post: &Post) -> bool {
+        self.preview || post.is_published(&self.today)
+    }
+}
+
+/// The visibility of the current visitor, provided by `App`.
+pub fn use_visibility() -> Resource<Visibility> {
+    expect_context()
+}
+
+/// Decided on the server, which knows the environment and the preview token.
+#[server]
+pub async fn get_visibility() -> Result<Visibility, ServerFnError> {
+    let mut visibility = preview::visibility();
+    visibility.posts = POST_INDEX
+        .visible(&visibility)
+        .filter(|post| !post.is_always_public())
+        .collect();
+    Ok(visibility)
+}
+
+/// Every post, newest first. In the browser, only those that are always public.
+pub struct PostIndex {
+    posts: &'static [Post],
+}
+
+impl PostIndex {
+    const fn new(posts: &'static [Post]) -> Self {
+        PostIndex { posts }
+    }
+
+    /// Posts `visibility` allows, newest first, including those it was sent.
+    pub fn visible<'a>(
+        &self,
+        visibility: &'a Visibility,
+    ) -> impl Iterator<Item = &'static Post> + 'a {
+        let mut posts: Vec<&'static Post> = self.posts.iter().collect();
+        for post in &visibility.posts {
+            // The server's index has them already.
+            if !posts.iter().any(|known| known.slug == post.slug) {
+                posts.push(post);
+            }
+        }
+        if posts.len() > self.posts.len() {
+            posts.sort_by(|a, b| b.date.cmp(a.date).then(a.slug.cmp(b.slug)));
+        }
+        posts
+            .into_iter()
+            .filter(move |post| visibility.allows(post))
+    }
+
+    pub fn latest(&self, visibility: &Visibility) -> Option<&'static Post> {
+        self.visible(visibility).next()
+    }
+
+    /// Every visible tag with the number of posts that have it, alphabetically.
+    pub fn tags(&self, visibility: &Visibility) -> Vec<(&'static str, usize)> {
+        let mut counts = BTreeMap::new();
+        for tag in self.visible(visibility).flat_map(|post| post.tags) {
+            *counts.entry(*tag).or_insert(0) += 1;
+        }
+        counts.into_iter().collect()
+    }
+
+    /// Visible posts with `tag`, newest first.
+    pub fn tagged<'a>(
+        &self,
+        tag: &'a str,
+        visibility: &'a Visibility,
+    ) -> impl Iterator<Item = &'static Post> + 'a {
+        self.visible(visibility)
+            .filter(move |post| post.tags.contains(&tag))
+    }
+
+    /// The post with `slug`, if `visibility` allows it.
+    pub fn get(&self, slug: &str, visibility: &Visibility) -> Option<&'static Post> {
+        self.visible(visibility).find(|post| post.slug == slug)
+    }
+
+    /// The post at `index` in the index order, hidden or not.
+    #[cfg(feature = "ssr")]
+    pub fn nth(&self, index: usize) -> Option<&'static Post> {
+        self.posts.get(index)
+    }
+}
+
+#[cfg(feature = "ssr")]
+include!(concat!(env!("OUT_DIR"), "/post_index.rs"));
+// Only the posts that are always public, so drafts stay out of the bundle.
+#[cfg(not(feature = "ssr"))]
+include!(concat!(env!("OUT_DIR"), "/public_post_index.rs"));
+
+/// The body of the post with the given slug, or `None` if there is no such
+/// post or the visitor may not see it.
+#[server]
+pub async fn get_post(slug: String) -> Result<Option<Vec<Block>>, ServerFnError> {
+    Ok(POST_INDEX
+        .get(&slug, &preview::visibility())
+        .and_then(|_| content::body(&slug)))
+}
+
+/// The current date in UTC as `YYYY-MM-DD`.
+#[cfg(feature = "ssr")]
+pub fn today() -> String {
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs());
+    // Howard Hinnant's days-to-civil algorithm.
+    let days = (secs / 86_400) as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+/// The current date in UTC as `YYYY-MM-DD`.
+#[cfg(not(feature = "ssr"))]
+pub fn today() -> String {
+    let iso = String::from(web_sys::js_sys::Date::new_0().to_iso_string());
+    iso.get(..10).unwrap_or_default().to_string()
+}
+/// Turns a `YYYY-MM-DD` date into e.g. `June 20, 2024`, leaving anything else as is.
+pub fn display_date(date: &str) -> String {
+    const MONTHS: [&str; 12] = [
+        "January",
+        "February",
+        "March",
+        "April",
+        "May",
+        "June",
+        "July",
+        "August",
+        "September",
+        "October",
+        "November",
+        "December",
+    ];
+
+    let mut parts = date.splitn(3, '-');
+    let parsed = (|| {
+        let year: u32 = parts.next()?.parse().ok()?;
+        let month: usize = parts.next()?.parse().ok()?;
+        let day: u32 = parts.next()?.parse().ok()?;
+        Some(format!(
+            "{} {day}, {year}",
+            MONTHS.get(month.checked_sub(1)?)?
+        ))
+    })();
+    parsed.unwrap_or_else(|| date.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const fn post(slug: &'static str, date: &'static str, draft: bool) -> Post {
+        Post {
+            slug,
+            title: slug,
+            date,
+            tags: &["rust"],
+            summary: "",
+            draft,
+            publish_at: None,
+        }
+    }
+
+    static INDEX: PostIndex = PostIndex::new(&[
+        post("newest", "2024-03-01", false),
+        post("oldest", "2024-01-01", false),
+    ]);
+    static DRAFT: Post = post("draft", "2024-02-01", true);
+
+    fn preview(posts: Vec<&'static Post>) -> Visibility {
+        Visibility {
+            today: "2024-06-01".to_string(),
+            preview: true,
+            posts,
+        }
+    }
+
+    #[test]
+    fn visible_merges_sent_posts_newest_first() {
+        let visibility = preview(vec![&DRAFT]);
+        let slugs: Vec<_> = INDEX.visible(&visibility).map(|post| post.slug).collect();
+        assert_eq!(slugs, ["newest", "draft", "oldest"]);
+        assert_eq!(INDEX.get("draft", &visibility), Some(&DRAFT));
+    }
+
+    #[test]
+    fn visible_skips_sent_posts_the_index_has() {
+        let visibility = preview(vec![&INDEX.posts[0]]);
+        assert_eq!(INDEX.visible(&visibility).count(), 2);
+    }
+
+    #[test]
+    fn sent_posts_survive_serialization() {
+        let visibility = preview(vec![&DRAFT]);
+        let json = serde_json::to_string(&visibility).unwrap();
+        let received: Visibility = serde_json::from_str(&json).unwrap();
+        assert_eq!(received, visibility);
+    }
+}
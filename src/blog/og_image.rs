@@ -0,0 +1,338 @@
+//! Link preview cards for posts: title, date and tags on the site's colors.
+//! A card is rendered on its first request and cached on disk, keyed by the
+//! post metadata it shows, so editing a post renders a new one.
+
+use std::path::PathBuf;
+use std::{env, fs, io};
+
+use ab_glyph::{point, Font, FontRef, GlyphId, PxScale, ScaleFont};
+use axum::extract::Path;
+use axum::http::{header, StatusCode};
+use axum::response::{IntoResponse, Response};
+use lazy_static::lazy_static;
+use leptos::logging::warn;
+
+use super::fnv::fnv1a;
+use super::{preview, Post, POST_INDEX};
+use crate::site::{AUTHOR, SITE_NAME};
+
+const WIDTH: u32 = 1200;
+const HEIGHT: u32 = 630;
+const MARGIN: f32 = 80.0;
+
+/// Bumped when the layout changes, so cached cards are rendered again.
+const LAYOUT_VERSION: u32 = 3;
+/// Font sizes tried for the title, largest first.
+const TITLE_SIZES: [f32; 3] = [72.0, 60.0, 48.0];
+const MAX_TITLE_LINES: usize = 3;
+
+type Rgb = [u8; 3];
+
+// Colors from `style/tailwind.css`.
+const BASE: Rgb = [0x1e, 0x1e, 0x2e];
+const SURFACE: Rgb = [0x36, 0x3a, 0x4f];
+const TEXT: Rgb = [0xca, 0xd3, 0xf5];
+const PEACH: Rgb = [0xf5, 0xa9, 0x7f];
+const MAROON: Rgb = [0xee, 0x99, 0xa0];
+const SKY: Rgb = [0x91, 0xd7, 0xe3];
+
+lazy_static! {
+    /// JetBrains Mono, the site font.
+    static ref MONO: FontRef<'static> =
+        FontRef::try_from_slice(include_bytes!("../../assets/fonts/JetBrainsMono-Regular.ttf"))
+            .expect("bundled font should parse");
+    /// From `OG_CACHE_DIR`, or a folder in the system's temporary directory.
+    static ref CACHE_DIR: PathBuf = env::var_os("OG_CACHE_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| env::temp_dir().join("rabbitnook-og"));
+}
+
+/// `GET /blog/og/{file}`, where `file` is `<slug>.png`.
+pub async fn og_image(Path(file): Path<String>) -> Response {
+    let visibility = preview::visibility();
+    let Some(post) = file
+        .strip_suffix(".png")
+        .and_then(|slug| POST_INDEX.get(slug, &visibility))
+    else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+
+    match tokio::task::spawn_blocking(move || cached(post)).await {
+        Ok(Ok(png)) => (
+            [
+                (header::CONTENT_TYPE, "image/png"),
+                (header::CACHE_CONTROL, "public, max-age=86400"),
+            ],
+            png,
+        )
+            .into_response(),
+        Ok(Err(err)) => {
+            warn!("rendering the card for {} failed: {err}", post.slug);
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+        Err(_) => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+    }
+}
+
+/// The card for `post`, from the cache if it has been rendered before. A cache
+/// that cannot be written to only costs rendering the card again.
+fn cached(post: &Post) -> io::Result<Vec<u8>> {
+    let path = CACHE_DIR.join(format!("{}-{:016x}.png", post.slug, fingerprint(post)));
+    if let Ok(png) = fs::read(&path) {
+        return Ok(png);
+    }
+
+    let png = render(post)?;
+    let store = || {
+        fs::create_dir_all(&*CACHE_DIR)?;
+        // Renamed into place, so a concurrent request never reads half a file.
+        let partial = path.with_extension(format!("{}.partial", std::process::id()));
+        fs::write(&partial, &png)?;
+        fs::rename(&partial, &path)
+    };
+    if let Err(err) = store() {
+        warn!("caching the card for {} failed: {err}", post.slug);
+    }
+    Ok(png)
+}
+
+/// FNV-1a of everything the card shows, stable across builds unlike `Hash`.
+fn fingerprint(post: &Post) -> u64 {
+    let layout = LAYOUT_VERSION.to_le_bytes();
+    let tags = post.tags.join(" ");
+    let fields = [post.title, post.date, &tags].map(str::as_bytes);
+    fnv1a(
+        layout
+            .iter()
+            .chain(fields.iter().flat_map(|field| field.iter().chain([&0]))),
+    )
+}
+
+fn render(post: &Post) -> io::Result<Vec<u8>> {
+    let mut canvas = Canvas::new(BASE);
+    canvas.fill_rect(0, 0, WIDTH, 12, PEACH);
+    canvas.fill_rect(0, HEIGHT - 4, WIDTH, 4, SURFACE);
+
+    canvas.text(SITE_NAME, MARGIN, MARGIN, 36.0, PEACH);
+
+    let (size, lines) = title_lines(post.title, WIDTH as f32 - 2.0 * MARGIN);
+    let mut y = MARGIN + 90.0;
+    for line in lines {
+        canvas.text(&line, MARGIN, y, size, TEXT);
+        y += size * 1.25;
+    }
+
+    let tags: Vec<String> = post.tags.iter().map(|tag| format!("#{tag}")).collect();
+    canvas.text(
+        &tags.join("  "),
+        MARGIN,
+        HEIGHT as f32 - MARGIN - 100.0,
+        30.0,
+        MAROON,
+    );
+    let bottom = HEIGHT as f32 - MARGIN - 40.0;
+    canvas.text(&post.display_date(), MARGIN, bottom, 32.0, SKY);
+    let author_x = WIDTH as f32 - MARGIN - text_width(AUTHOR, 32.0);
+    canvas.text(AUTHOR, author_x, bottom, 32.0, TEXT);
+
+    canvas.encode()
+}
+
+/// The largest size from [`TITLE_SIZES`] that fits `title` on
+/// [`MAX_TITLE_LINES`] lines, and those lines. Titles too long even at the
+/// smallest size are cut off with an ellipsis.
+fn title_lines(title: &str, max_width: f32) -> (f32, Vec<String>) {
+    let fitting = TITLE_SIZES
+        .into_iter()
+        .map(|size| (size, wrap(title, size, max_width)))
+        .find(|(_, lines)| lines.len() <= MAX_TITLE_LINES);
+    if let Some(fitting) = fitting {
+        return fitting;
+    }
+
+    let size = TITLE_SIZES[TITLE_SIZES.len() - 1];
+    let mut lines = wrap(title, size, max_width);
+    lines.truncate(MAX_TITLE_LINES);
+    if let Some(last) = lines.last_mut() {
+        while !last.is_empty() && text_width(&format!("{last}…"), size) > max_width {
+            last.pop();
+        }
+        last.truncate(last.trim_end().len());
+        last.push('…');
+    }
+    (size, lines)
+}
+
+/// Splits `text` into lines no wider than `max_width` at `size`, breaking
+/// between words, or inside words too long for a line of their own. Words
+/// the font can't draw any of, such as `❤️`, are left out rather than
+/// leaving a gap.
+fn wrap(text: &str, size: f32, max_width: f32) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    let words = text
+        .split_whitespace()
+        .filter(|word| word.chars().any(|c| glyph_id(c).is_some()));
+    for word in words {
+        match lines.last_mut() {
+            Some(line) if text_width(&format!("{line} {word}"), size) <= max_width => {
+                line.push(' ');
+                line.push_str(word);
+            }
+            _ => {
+                lines.push(String::new());
+                for c in word.chars() {
+                    let line = lines.last_mut().expect("just pushed");
+                    if !line.is_empty() && text_width(&format!("{line}{c}"), size) > max_width {
+                        lines.push(c.to_string());
+                    } else {
+                        line.push(c);
+                    }
+                }
+            }
+        }
+    }
+    lines
+}
+
+/// The glyph for `c`, `None` for characters the font lacks, such as emoji.
+fn glyph_id(c: char) -> Option<GlyphId> {
+    Some(MONO.glyph_id(c)).filter(|id| id.0 != 0)
+}
+
+fn text_width(text: &str, size: f32) -> f32 {
+    let scaled = MONO.as_scaled(PxScale::from(size));
+    text.chars()
+        .filter_map(glyph_id)
+        .map(|id| scaled.h_advance(id))
+        .sum()
+}
+
+/// An RGB image being drawn.
+struct Canvas {
+    pixels: Vec<u8>,
+}
+
+impl Canvas {
+    fn new(background: Rgb) -> Self {
+        Canvas {
+            pixels: background.repeat((WIDTH * HEIGHT) as usize),
+        }
+    }
+
+    fn fill_rect(&mut self, x: u32, y: u32, width: u32, height: u32, color: Rgb) {
+        for y in y..(y + height).min(HEIGHT) {
+            for x in x..(x + width).min(WIDTH) {
+                self.blend(x, y, color, 1.0);
+            }
+        }
+    }
+
+    /// Mixes `color` into the pixel at `x`, `y` by `coverage`, 0 to 1.
+    fn blend(&mut self, x: u32, y: u32, color: Rgb, coverage: f32) {
+        if x >= WIDTH || y >= HEIGHT {
+            return;
+        }
+        let i = ((y * WIDTH + x) * 3) as usize;
+        for (channel, value) in self.pixels[i..i + 3].iter_mut().zip(color) {
+            let mixed = f32::from(*channel) + (f32::from(value) - f32::from(*channel)) * coverage;
+            *channel = mixed.round() as u8;
+        }
+    }
+
+    /// Draws one line of `text` with the top of its line box at `y`.
+    fn text(&mut self, text: &str, x: f32, y: f32, size: f32, color: Rgb) {
+        let scale = PxScale::from(size);
+        let scaled = MONO.as_scaled(scale);
+        let baseline = y + scaled.ascent();
+        let mut x = x;
+        for id in text.chars().filter_map(glyph_id) {
+            if let Some(outline) =
+                MONO.outline_glyph(id.with_scale_and_position(scale, point(x, baseline)))
+            {
+                let bounds = outline.px_bounds();
+                outline.draw(|gx, gy, coverage| {
+                    let (px, py) = (
+                        bounds.min.x as i64 + gx as i64,
+                        bounds.min.y as i64 + gy as i64,
+                    );
+                    if px >= 0 && py >= 0 {
+                        self.blend(px as u32, py as u32, color, coverage.min(1.0));
+                    }
+                });
+            }
+            x += scaled.h_advance(id);
+        }
+    }
+
+    fn encode(self) -> io::Result<Vec<u8>> {
+        let mut png = Vec::new();
+        let mut encoder = png::Encoder::new(&mut png, WIDTH, HEIGHT);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&self.pixels)?;
+        writer.finish()?;
+        Ok(png)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fits(lines: &[String], size: f32, max_width: f32) -> bool {
+        lines.iter().all(|line| text_width(line, size) <= max_width)
+    }
+
+    #[test]
+    fn wraps_between_words() {
+        let width = text_width("Hello world", 48.0);
+        assert_eq!(wrap("Hello   world", 48.0, width), ["Hello world"]);
+        assert_eq!(wrap("Hello world", 48.0, width - 1.0), ["Hello", "world"]);
+        assert_eq!(wrap("I ❤️ Rust", 48.0, 1000.0), ["I Rust"]);
+        assert!(wrap("", 48.0, 1000.0).is_empty());
+    }
+
+    #[test]
+    fn breaks_long_words() {
+        let width = text_width("abcd", 48.0);
+        let lines = wrap("a abcdefghij b", 48.0, width);
+        assert_eq!(lines, ["a", "abcd", "efgh", "ij b"]);
+        assert!(fits(&lines, 48.0, width));
+    }
+
+    #[test]
+    fn shrinks_titles_to_fit() {
+        let max_width = WIDTH as f32 - 2.0 * MARGIN;
+        assert_eq!(
+            title_lines("Short title", max_width),
+            (72.0, vec!["Short title".to_string()])
+        );
+
+        let title = "word ".repeat(30);
+        let (size, lines) = title_lines(&title, max_width);
+        assert!(size < 72.0);
+        assert!(lines.len() <= MAX_TITLE_LINES);
+        assert!(fits(&lines, size, max_width));
+    }
+
+    #[test]
+    fn cuts_off_long_titles() {
+        let max_width = WIDTH as f32 - 2.0 * MARGIN;
+        let title = "word ".repeat(100);
+        let (size, lines) = title_lines(&title, max_width);
+        assert_eq!(size, 48.0);
+        assert_eq!(lines.len(), MAX_TITLE_LINES);
+        assert!(lines[2].ends_with("word…"), "{lines:?}");
+        assert!(fits(&lines, size, max_width));
+
+        let full = wrap(&title, 48.0, max_width);
+        assert_eq!(lines[..2], full[..2]);
+        assert!(full[2].starts_with(lines[2].trim_end_matches('…')));
+
+        // The ellipsis takes the place of what would not fit next to it.
+        let width = text_width("abcdef", 48.0);
+        let (_, lines) = title_lines(&"abcdef ".repeat(12), width);
+        assert_eq!(lines, ["abcdef", "abcdef", "abcde…"]);
+    }
+}
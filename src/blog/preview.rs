@@ -0,0 +1,98 @@
+//! Who gets to see drafts and scheduled posts: everyone when `LEPTOS_ENV` is
+//! set to `DEV`, and anyone who opened a page with `?preview=<PREVIEW_TOKEN>`
+//! elsewhere.
+
+use std::env;
+
+use http::header::{COOKIE, SET_COOKIE};
+use http::request::Parts;
+use http::HeaderValue;
+use lazy_static::lazy_static;
+use leptos::prelude::*;
+use leptos_axum::ResponseOptions;
+
+use super::Visibility;
+use crate::site::cookie_attributes;
+
+/// Name of both the query parameter and the cookie that remembers it.
+const PREVIEW: &str = "preview";
+
+lazy_static! {
+    /// Read from the environment rather than the Leptos config, which falls
+    /// back to DEV when `LEPTOS_ENV` is unset and would publish drafts.
+    static ref LEPTOS_ENV: Option<String> = env::var("LEPTOS_ENV").ok();
+    static ref TOKEN: Option<String> = env::var("PREVIEW_TOKEN")
+        .ok()
+        .filter(|token| !token.is_empty());
+}
+
+/// The visibility for the request being handled. Outside a Leptos request,
+/// e.g. in the feed handlers, only DEV enables previews.
+pub fn visibility() -> Visibility {
+    visibility_for(LEPTOS_ENV.as_deref(), has_token())
+}
+
+/// Previews need `leptos_env` to be DEV, spelled the ways Leptos accepts, or
+/// the token. Anything else, including no `LEPTOS_ENV`, hides them.
+fn visibility_for(leptos_env: Option<&str>, has_token: bool) -> Visibility {
+    let dev = leptos_env.is_some_and(|env| {
+        env.eq_ignore_ascii_case("dev") || env.eq_ignore_ascii_case("development")
+    });
+    Visibility {
+        preview: dev || has_token,
+        ..Visibility::public()
+    }
+}
+
+/// Whether the request carries the preview token, in the query or in the
+/// cookie set the first time it did.
+fn has_token() -> bool {
+    let (Some(token), Some(parts)) = (TOKEN.as_deref(), use_context::<Parts>()) else {
+        return false;
+    };
+
+    let in_query = parts.uri.query().is_some_and(|query| {
+        query
+            .split('&')
+            .any(|pair| pair.split_once('=') == Some((PREVIEW, token)))
+    });
+    if in_query {
+        let cookie = format!("{PREVIEW}={token}; {}", cookie_attributes());
+        if let (Some(response), Ok(cookie)) = (
+            use_context::<ResponseOptions>(),
+            HeaderValue::from_str(&cookie),
+        ) {
+            response.append_header(SET_COOKIE, cookie);
+        }
+        return true;
+    }
+
+    parts
+        .headers
+        .get_all(COOKIE)
+        .iter()
+        .filter_map(|header| header.to_str().ok())
+        .flat_map(|header| header.split(';'))
+        .any(|pair| pair.trim().split_once('=') == Some((PREVIEW, token)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::visibility_for;
+
+    #[test]
+    fn previews_fail_closed() {
+        assert!(!visibility_for(None, false).preview);
+        assert!(!visibility_for(Some(""), false).preview);
+        assert!(!visibility_for(Some("PROD"), false).preview);
+        assert!(!visibility_for(Some("production"), false).preview);
+    }
+
+    #[test]
+    fn dev_or_the_token_enables_previews() {
+        assert!(visibility_for(Some("DEV"), false).preview);
+        assert!(visibility_for(Some("development"), false).preview);
+        assert!(visibility_for(None, true).preview);
+        assert!(visibility_for(Some("PROD"), true).preview);
+    }
+}
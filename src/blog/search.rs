@@ -0,0 +1,323 @@
+//! Full-text search over posts, backed by an inverted index that `build.rs`
+//! builds from the post content. The index stays on the server, the browser
+//! only gets the hits.
+
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// A post matching a query.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Hit {
+    pub slug: String,
+    /// Text around the first match, with `true` for the parts that matched.
+    pub snippet: Vec<(String, bool)>,
+}
+
+/// Posts the visitor may see matching `query`, best match first.
+#[server]
+pub async fn search_posts(query: String) -> Result<Vec<Hit>, ServerFnError> {
+    Ok(search(&query, &super::preview::visibility()))
+}
+
+#[cfg(feature = "ssr")]
+pub use index::search;
+
+#[cfg(feature = "ssr")]
+mod index {
+    use std::cmp::Reverse;
+    use std::collections::BTreeMap;
+
+    use super::Hit;
+    use crate::blog::words::{normalize, words};
+    use crate::blog::{PostIndex, Visibility, POST_INDEX};
+
+    // Defines `SEARCH_INDEX`, see `build/search.rs`.
+    include!(concat!(env!("OUT_DIR"), "/search_index.rs"));
+
+    /// Bytes of context kept around the first match in a snippet.
+    const CONTEXT_BEFORE: usize = 60;
+    const CONTEXT_AFTER: usize = 140;
+
+    /// Every word of every post and the posts it appears in.
+    pub struct SearchIndex {
+        /// Summary and body text of each post, in `POST_INDEX` order.
+        texts: &'static [&'static str],
+        /// Sorted terms with `(post, score)` for each post they appear in.
+        terms: &'static [(&'static str, &'static [(usize, u32)])],
+    }
+
+    /// Visible posts containing every word of `query`, best match first. A
+    /// word also matches longer words it starts, so `conf` finds `config`.
+    pub fn search(query: &str, visibility: &Visibility) -> Vec<Hit> {
+        SEARCH_INDEX.search(&POST_INDEX, query, visibility)
+    }
+
+    impl SearchIndex {
+        const fn new(
+            texts: &'static [&'static str],
+            terms: &'static [(&'static str, &'static [(usize, u32)])],
+        ) -> Self {
+            SearchIndex { texts, terms }
+        }
+
+        /// Searches `posts`, the index the search index was built from.
+        fn search(&self, posts: &PostIndex, query: &str, visibility: &Visibility) -> Vec<Hit> {
+            let query: Vec<String> = words(query).map(|(_, word)| normalize(word)).collect();
+            if query.is_empty() {
+                return Vec::new();
+            }
+
+            let mut scores: Option<BTreeMap<usize, u32>> = None;
+            for word in &query {
+                let mut found = BTreeMap::new();
+                for (_, postings) in self.prefixed(word) {
+                    for (post, score) in postings.iter() {
+                        *found.entry(*post).or_insert(0) += score;
+                    }
+                }
+                scores = Some(match scores {
+                    None => found,
+                    Some(scores) => scores
+                        .into_iter()
+                        .filter_map(|(post, score)| Some((post, score + found.get(&post)?)))
+                        .collect(),
+                });
+            }
+
+            let mut hits: Vec<(u32, Hit)> = scores
+                .unwrap_or_default()
+                .into_iter()
+                .filter_map(|(post, score)| {
+                    let slug = posts.nth(post).filter(|post| visibility.allows(post))?.slug;
+                    Some((
+                        score,
+                        Hit {
+                            slug: slug.to_string(),
+                            snippet: snippet(self.texts.get(post)?, &query),
+                        },
+                    ))
+                })
+                .collect();
+            // Stable, so equal scores stay newest first.
+            hits.sort_by_key(|(score, _)| Reverse(*score));
+            hits.into_iter().map(|(_, hit)| hit).collect()
+        }
+
+        /// Terms starting with `prefix`.
+        fn prefixed(&self, prefix: &str) -> &'static [(&'static str, &'static [(usize, u32)])] {
+            let start = self.terms.partition_point(|(term, _)| *term < prefix);
+            let len = self.terms[start..]
+                .iter()
+                .take_while(|(term, _)| term.starts_with(prefix))
+                .count();
+            &self.terms[start..start + len]
+        }
+    }
+
+    /// The part of `text` around the first word matching `query`, or its start
+    /// when only the title or tags matched.
+    fn snippet(text: &str, query: &[String]) -> Vec<(String, bool)> {
+        let matches = |word: &str| {
+            let word = normalize(word);
+            query.iter().any(|query| word.starts_with(query.as_str()))
+        };
+        let first = words(text)
+            .find(|(_, word)| matches(word))
+            .map_or(0, |(start, _)| start);
+
+        let mut start = floor_char_boundary(text, first.saturating_sub(CONTEXT_BEFORE));
+        if start > 0 {
+            // Start at a whole word.
+            start = text[start..first]
+                .find(char::is_whitespace)
+                .map_or(first, |space| start + space + 1);
+        }
+        let mut end = floor_char_boundary(text, (first + CONTEXT_AFTER).min(text.len()));
+        if end < text.len() {
+            end = text[..end]
+                .rfind(char::is_whitespace)
+                .unwrap_or(end)
+                .max(first);
+        }
+
+        let mut parts = Vec::new();
+        if start > 0 {
+            parts.push(("…", false));
+        }
+        let mut plain = start;
+        for (offset, word) in words(&text[start..end]) {
+            if matches(word) {
+                let offset = start + offset;
+                parts.push((&text[plain..offset], false));
+                parts.push((word, true));
+                plain = offset + word.len();
+            }
+        }
+        parts.push((&text[plain..end], false));
+        if end < text.len() {
+            parts.push(("…", false));
+        }
+        parts
+            .into_iter()
+            .filter(|(part, _)| !part.is_empty())
+            .map(|(part, matched)| (part.to_string(), matched))
+            .collect()
+    }
+
+    fn floor_char_boundary(text: &str, mut index: usize) -> usize {
+        while !text.is_char_boundary(index) {
+            index -= 1;
+        }
+        index
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use crate::blog::Post;
+
+        const fn post(slug: &'static str, draft: bool, publish_at: Option<&'static str>) -> Post {
+            Post {
+                slug,
+                title: slug,
+                date: "2024-01-01",
+                tags: &[],
+                summary: "",
+                draft,
+                publish_at,
+            }
+        }
+
+        static POSTS: PostIndex = PostIndex::new(&[
+            post("leptos", false, None),
+            post("relay", false, None),
+            post("draft", true, None),
+            post("scheduled", false, Some("2024-07-01")),
+        ]);
+
+        static INDEX: SearchIndex = SearchIndex::new(
+            &[
+                "Leptos renders on the server and hydrates in the browser.",
+                "An ESP32 relay configured over AWS IoT, written in Rust.",
+                "A draft about Rust configuration.",
+                "Scheduled Rust release notes.",
+            ],
+            &[
+                ("aws", &[(1, 1)]),
+                ("browser", &[(0, 1)]),
+                ("configuration", &[(2, 1)]),
+                ("configured", &[(1, 1)]),
+                ("leptos", &[(0, 6)]),
+                ("relay", &[(1, 6)]),
+                ("rust", &[(0, 3), (1, 4), (2, 1), (3, 1)]),
+                ("server", &[(0, 1)]),
+            ],
+        );
+
+        fn visibility(preview: bool) -> Visibility {
+            Visibility {
+                today: "2024-06-01".to_string(),
+                preview,
+                posts: Vec::new(),
+            }
+        }
+
+        fn slugs(query: &str, preview: bool) -> Vec<String> {
+            INDEX
+                .search(&POSTS, query, &visibility(preview))
+                .into_iter()
+                .map(|hit| hit.slug)
+                .collect()
+        }
+
+        #[test]
+        fn ranks_by_score() {
+            assert_eq!(slugs("rust", false), ["relay", "leptos"]);
+            assert_eq!(slugs("RUST", false), slugs("rust", false));
+            assert!(slugs("", false).is_empty());
+            assert!(slugs("python", false).is_empty());
+        }
+
+        #[test]
+        fn needs_every_word() {
+            assert_eq!(slugs("rust aws", false), ["relay"]);
+            assert_eq!(slugs("rust server", false), ["leptos"]);
+            assert!(slugs("aws server", false).is_empty());
+        }
+
+        #[test]
+        fn words_match_as_prefixes() {
+            assert_eq!(slugs("config", true), ["relay", "draft"]);
+            assert_eq!(slugs("lep", false), ["leptos"]);
+            assert!(slugs("configs", true).is_empty());
+        }
+
+        #[test]
+        fn hides_drafts_and_scheduled_posts_without_preview() {
+            assert_eq!(
+                slugs("rust", true),
+                ["relay", "leptos", "draft", "scheduled"]
+            );
+            assert!(slugs("configuration", false).is_empty());
+            let published = Visibility {
+                today: "2024-07-01".to_string(),
+                ..visibility(false)
+            };
+            let hits = INDEX.search(&POSTS, "rust", &published);
+            assert_eq!(hits.last().map(|hit| hit.slug.as_str()), Some("scheduled"));
+        }
+
+        #[test]
+        fn snippets_mark_matches() {
+            let hits = INDEX.search(&POSTS, "aws rel", &visibility(false));
+            assert_eq!(
+                hits[0].snippet,
+                [
+                    ("An ESP32 ".to_string(), false),
+                    ("relay".to_string(), true),
+                    (" configured over ".to_string(), false),
+                    ("AWS".to_string(), true),
+                    (" IoT, written in Rust.".to_string(), false),
+                ]
+            );
+        }
+
+        fn text(parts: &[(String, bool)]) -> String {
+            parts.iter().map(|(part, _)| part.as_str()).collect()
+        }
+
+        #[test]
+        fn snippets_cut_at_words_and_char_boundaries() {
+            let query = ["mitt".to_string()];
+            // The context ends fall inside multi-byte characters.
+            let body = format!("{} mitt {}", "åäö ".repeat(30), "ünï ".repeat(60));
+            let parts = snippet(&body, &query);
+            assert_eq!(parts.first(), Some(&("…".to_string(), false)));
+            assert_eq!(parts.last(), Some(&("…".to_string(), false)));
+            assert!(parts.contains(&("mitt".to_string(), true)));
+            let shown = text(&parts);
+            assert!(shown.starts_with("…åäö "), "{shown}");
+            assert!(shown.trim_end_matches('…').ends_with("ünï"), "{shown}");
+
+            // Long words on either side leave just the match.
+            let body = format!("{} mitt {}", "é".repeat(100), "ö".repeat(100));
+            assert_eq!(text(&snippet(&body, &query)), "…mitt…");
+        }
+
+        #[test]
+        fn snippets_at_the_start_and_end_of_the_text() {
+            let query = ["åsa".to_string()];
+            assert_eq!(
+                snippet("Åsa är här", &query),
+                [("Åsa".to_string(), true), (" är här".to_string(), false)]
+            );
+            assert_eq!(
+                snippet("Hej Åsa", &query),
+                [("Hej ".to_string(), false), ("Åsa".to_string(), true)]
+            );
+            // Only the title matched, so the text is shown from its start.
+            let long = "ö".repeat(100);
+            assert_eq!(text(&snippet(&long, &query)), format!("{}…", &long[..140]));
+        }
+    }
+}
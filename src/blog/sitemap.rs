@@ -0,0 +1,257 @@
+//! `sitemap.xml` and `robots.txt` for search engines. Like the feeds they are
+//! built per request, so scheduled posts are listed once they are out.
+
+use std::env;
+use std::sync::Arc;
+
+use axum::http::header;
+use axum::response::{IntoResponse, Response};
+use lazy_static::lazy_static;
+use leptos_axum::AxumRouteListing;
+
+use super::feed::escape;
+use super::{preview, PostIndex, Visibility, POST_INDEX};
+use crate::site::SITE_ORIGIN;
+
+/// Pages left out of the sitemap and disallowed by default in `robots.txt`.
+const UNINDEXED: &[&str] = &["/blog/search"];
+
+lazy_static! {
+    /// Paths crawlers should skip, from the comma separated `ROBOTS_DISALLOW`.
+    /// `/` keeps them off the whole site, e.g. on a staging server.
+    static ref DISALLOW: Vec<String> = match env::var("ROBOTS_DISALLOW") {
+        Ok(paths) => paths
+            .split(',')
+            .map(str::trim)
+            .filter(|path| !path.is_empty())
+            .map(str::to_string)
+            .collect(),
+        Err(_) => UNINDEXED.iter().map(|path| path.to_string()).collect(),
+    };
+}
+
+/// The app's routes that have no parameters and are worth indexing: pages,
+/// not server functions under `/api` or files such as the feeds.
+pub fn static_paths(routes: &[AxumRouteListing]) -> Arc<[String]> {
+    routes
+        .iter()
+        .map(|route| match route.path() {
+            "" => "/",
+            path => path,
+        })
+        .filter(|path| {
+            !path.contains(['{', ':', '*'])
+                && !UNINDEXED.contains(path)
+                && path.split('/').nth(1) != Some("api")
+                && !path
+                    .rsplit('/')
+                    .next()
+                    .is_some_and(|name| name.contains('.'))
+        })
+        .map(str::to_string)
+        .collect()
+}
+
+/// `GET /sitemap.xml`, with `paths` from [`static_paths`], each post and
+/// each tag page.
+pub async fn sitemap(paths: Arc<[String]>) -> Response {
+    respond(
+        "application/xml; charset=utf-8",
+        urlset(&paths, &POST_INDEX, &preview::visibility()),
+    )
+}
+
+/// The sitemap of `paths` and what `visibility` allows of `posts`.
+fn urlset(paths: &[String], posts: &PostIndex, visibility: &Visibility) -> String {
+    let latest = posts.latest(visibility).map(|post| post.date);
+
+    let mut urls: Vec<(String, Option<&str>)> = paths
+        .iter()
+        .map(|path| {
+            // The home page shows the latest post in its terminal.
+            let lastmod = matches!(path.as_str(), "/" | "/blog" | "/blog/tags")
+                .then_some(latest)
+                .flatten();
+            (path.clone(), lastmod)
+        })
+        .collect();
+    urls.extend(
+        posts
+            .visible(visibility)
+            .map(|post| (post.path(), Some(post.date))),
+    );
+    urls.extend(posts.tags(visibility).into_iter().map(|(tag, _)| {
+        let newest = posts.tagged(tag, visibility).next();
+        (format!("/blog/tags/{tag}"), newest.map(|post| post.date))
+    }));
+
+    let mut xml = concat!(
+        "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n",
+        "<urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n",
+    )
+    .to_string();
+    for (path, lastmod) in urls {
+        xml += &format!(
+            "<url><loc>{}</loc>",
+            escape(&format!("{}{path}", *SITE_ORIGIN))
+        );
+        if let Some(date) = lastmod {
+            xml += &format!("<lastmod>{date}</lastmod>");
+        }
+        xml += "</url>\n";
+    }
+    xml += "</urlset>\n";
+    xml
+}
+
+/// `GET /robots.txt`, pointing crawlers at the sitemap.
+pub async fn robots() -> Response {
+    let mut txt = "User-agent: *\n".to_string();
+    if DISALLOW.is_empty() {
+        txt += "Disallow:\n";
+    }
+    for path in DISALLOW.iter() {
+        txt += &format!("Disallow: {path}\n");
+    }
+    txt += &format!("\nSitemap: {}/sitemap.xml\n", *SITE_ORIGIN);
+
+    respond("text/plain; charset=utf-8", txt)
+}
+
+fn respond(content_type: &'static str, body: String) -> Response {
+    (
+        [
+            (header::CONTENT_TYPE, content_type),
+            (header::CACHE_CONTROL, "public, max-age=3600"),
+        ],
+        body,
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use leptos_router::{Method, SsrMode};
+
+    use super::*;
+    use crate::blog::Post;
+
+    const fn post(
+        slug: &'static str,
+        date: &'static str,
+        tags: &'static [&'static str],
+        draft: bool,
+        publish_at: Option<&'static str>,
+    ) -> Post {
+        Post {
+            slug,
+            title: slug,
+            date,
+            tags,
+            summary: "",
+            draft,
+            publish_at,
+        }
+    }
+
+    /// Newest first, like `build.rs` sorts them.
+    static POSTS: PostIndex = PostIndex::new(&[
+        post(
+            "scheduled",
+            "2024-05-03",
+            &["soon"],
+            false,
+            Some("2024-07-01"),
+        ),
+        post("draft", "2024-05-02", &["secret"], true, None),
+        post("new", "2024-05-01", &["rust"], false, None),
+        post("old", "2024-01-01", &["esp32", "rust"], false, None),
+    ]);
+
+    fn visibility(preview: bool) -> Visibility {
+        Visibility {
+            today: "2024-06-01".to_string(),
+            preview,
+            posts: Vec::new(),
+        }
+    }
+
+    /// Each `<loc>` path of the sitemap, with its `<lastmod>`.
+    fn urls(preview: bool) -> Vec<(String, Option<String>)> {
+        let paths = ["/".to_string(), "/blog".to_string(), "/about".to_string()];
+        urlset(&paths, &POSTS, &visibility(preview))
+            .lines()
+            .filter_map(|line| {
+                let rest = line.strip_prefix("<url><loc>")?;
+                let (loc, rest) = rest.split_once("</loc>")?;
+                let path = loc.strip_prefix(SITE_ORIGIN.as_str())?.to_string();
+                let lastmod = rest
+                    .strip_prefix("<lastmod>")
+                    .and_then(|rest| rest.split_once("</lastmod>"))
+                    .map(|(date, _)| date.to_string());
+                Some((path, lastmod))
+            })
+            .collect()
+    }
+
+    fn url(path: &str, lastmod: Option<&str>) -> (String, Option<String>) {
+        (path.to_string(), lastmod.map(str::to_string))
+    }
+
+    #[test]
+    fn keeps_static_pages() {
+        let routes: Vec<AxumRouteListing> = [
+            "",
+            "/blog",
+            "/blog/search",
+            "/blog/tags",
+            "/blog/tags/:tag",
+            "/blog/{slug}",
+            "/files/*path",
+            "/api/get_visibility",
+            "/api",
+            "/blog/rss.xml",
+            "/blog/atom.xml",
+            "/apiary",
+        ]
+        .into_iter()
+        .map(|path| {
+            AxumRouteListing::new(path.to_string(), SsrMode::Async, [Method::Get], Vec::new())
+        })
+        .collect();
+        assert_eq!(
+            *static_paths(&routes),
+            ["/", "/blog", "/blog/tags", "/apiary"].map(str::to_string)
+        );
+    }
+
+    #[test]
+    fn lists_published_posts_and_their_tags() {
+        assert_eq!(
+            urls(false),
+            [
+                url("/", Some("2024-05-01")),
+                url("/blog", Some("2024-05-01")),
+                url("/about", None),
+                url("/blog/new", Some("2024-05-01")),
+                url("/blog/old", Some("2024-01-01")),
+                url("/blog/tags/esp32", Some("2024-01-01")),
+                url("/blog/tags/rust", Some("2024-05-01")),
+            ]
+        );
+    }
+
+    #[test]
+    fn previews_list_drafts_and_scheduled_posts() {
+        let urls = urls(true);
+        for path in [
+            "/blog/draft",
+            "/blog/scheduled",
+            "/blog/tags/secret",
+            "/blog/tags/soon",
+        ] {
+            assert!(urls.iter().any(|(url, _)| url == path), "{path}");
+        }
+        assert_eq!(urls[0], url("/", Some("2024-05-03")));
+    }
+}
@@ -0,0 +1,14 @@
+//! Splitting text into searchable words, shared by `build.rs`, which builds
+//! the search index, and the search itself.
+
+/// Every run of letters and digits in `text` with its byte offset.
+pub fn words(text: &str) -> impl Iterator<Item = (usize, &str)> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(move |word| (word.as_ptr() as usize - text.as_ptr() as usize, word))
+}
+
+/// The form words are indexed and looked up in.
+pub fn normalize(word: &str) -> String {
+    word.to_lowercase()
+}
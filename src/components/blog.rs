@@ -2,40 +2,97 @@ use leptos::prelude::*;
 use leptos::{component, view, IntoView};
 use leptos_router::components::A;
 
+use crate::blog::{use_visibility, Post, POST_INDEX};
 use crate::components::links::Links;
+use crate::components::search::SearchBox;
+use crate::components::seo::Seo;
 
 #[component]
 pub fn Blog() -> impl IntoView {
+    let visibility = use_visibility();
+    let posts = move || {
+        Suspend::new(async move {
+            let visibility = visibility.await;
+            POST_INDEX
+                .visible(&visibility)
+                .map(|post| view! { <PostCard post=post /> })
+                .collect_view()
+        })
+    };
+
     view! {
+        <Seo
+            title="Blog"
+            description="Posts about Rust, embedded development and hardware hacking."
+            path="/blog"
+        />
         <div class="flex md:flex-row flex-col min-h-screen w-full bg-base items-center justify-center">
             <div class="md:w-2/3 w-5/6 flex flex-col justify-center items-center">
 
-                <h1 class="my-10 underline">Posts</h1>
-                <A href="/blog/nix-frame-hack">
-                    <div class="flex flex-col mt-10 border rounded-md w-full">
-                        <h1 class="mx-4 mt-2 text-xl hover:text-maroon">NixPlay frame hacking</h1>
-                        <p class="mx-4 my-2">April 26, 2025</p>
-
-                    </div>
-                </A>
-                <A href="/blog/esp32-relay">
-                    <div class="flex flex-col my-10 border rounded-md w-full">
-                        <h1 class="mx-4 mt-2 text-xl hover:text-maroon">
-                            "Rust ""❤️"" Esp32 remote relay using AWS IoT"
-                        </h1>
-                        <p class="mx-4 my-2">June 20, 2024</p>
-                    </div>
+                <h1 class="mt-10 underline">Posts</h1>
+                <SearchBox />
+                <A href="/blog/tags">
+                    <p class="mt-2 hover:text-maroon">Browse by tag</p>
                 </A>
-                <Links />
-                <div class="w-44 mb-6">
-                    <a href="https://ko-fi.com/safstromo" target="_blank">
-                        <img src="../kofi_button_blue.png" />
-                    </a>
+                <div class="flex flex-col items-center mb-10">
+                    <Suspense fallback=move || view! { <p class="mt-10">"Loading posts..."</p> }>
+                        {posts}
+                    </Suspense>
                 </div>
-                <A href="/">
-                    <p class="underline">Home</p>
-                </A>
+                <BlogFooter />
+            </div>
+        </div>
+    }
+}
+
+/// A post in a listing: title, date, summary and tags.
+#[component]
+pub fn PostCard(post: &'static Post) -> impl IntoView {
+    view! {
+        <div class="flex flex-col mt-10 border rounded-md w-full">
+            <A href=post.path()>
+                <h1 class="mx-4 mt-2 text-xl hover:text-maroon">{post.title}</h1>
+            </A>
+            <p class="mx-4 mt-2">{post.display_date()}</p>
+            <p class="mx-4 my-2">{post.summary}</p>
+            <div class="mx-4 mb-2">
+                <TagChips tags=post.tags />
             </div>
         </div>
     }
 }
+
+/// Links to the listing of each tag.
+#[component]
+pub fn TagChips(tags: &'static [&'static str]) -> impl IntoView {
+    view! {
+        <ul class="flex flex-wrap gap-2">
+            {tags
+                .iter()
+                .map(|tag| {
+                    view! {
+                        <li class="px-2 text-sm border border-peach rounded-md hover:text-maroon">
+                            <A href=format!("/blog/tags/{tag}")>{format!("#{tag}")}</A>
+                        </li>
+                    }
+                })
+                .collect_view()}
+        </ul>
+    }
+}
+
+/// Links shown at the bottom of every blog page.
+#[component]
+pub fn BlogFooter() -> impl IntoView {
+    view! {
+        <Links />
+        <div class="w-44 mb-6">
+            <a href="https://ko-fi.com/safstromo" target="_blank">
+                <img src="/kofi_button_blue.png" />
+            </a>
+        </div>
+        <A href="/">
+            <p class="underline">Home</p>
+        </A>
+    }
+}
@@ -0,0 +1,161 @@
+use leptos::prelude::*;
+use leptos_router::hooks::use_params_map;
+
+use crate::blog::markdown::{toc, Block, Inline};
+use crate::blog::{get_post, use_visibility, POST_INDEX};
+use crate::components::blog::{BlogFooter, TagChips};
+use crate::components::code_block::CodeBlock;
+use crate::components::error_pages::NotFound;
+use crate::components::image::Image;
+use crate::components::seo::Seo;
+use crate::components::toc::{HeadingLinks, TableOfContents};
+
+/// Any post under `content/blog`, picked by the `:slug` in the route.
+#[component]
+pub fn BlogPost() -> impl IntoView {
+    let params = use_params_map();
+    let slug = move || params.read().get("slug").unwrap_or_default();
+    let body = Resource::new(slug, get_post);
+    let visibility = use_visibility();
+
+    let blog_view = move || {
+        Suspend::new(async move {
+            let visibility = visibility.await;
+            let (Some(post), Some(body)) = (POST_INDEX.get(&slug(), &visibility), body.await?)
+            else {
+                return Ok(view! { <NotFound /> }.into_any());
+            };
+            // A single section does not need one.
+            let toc = Some(toc(&body)).filter(|entries| entries.len() > 1);
+            Ok::<_, ServerFnError>(view! {
+                <Seo title=post.title description=post.summary path=post.path() post=post />
+                <div class="flex lg:flex-row-reverse flex-col w-full lg:gap-8">
+                    {toc
+                        .map(|entries| {
+                            view! {
+                                <aside class="lg:sticky lg:top-4 lg:self-start lg:w-56 shrink-0 mt-8 lg:max-h-screen overflow-y-auto">
+                                    <TableOfContents entries />
+                                </aside>
+                            }
+                        })}
+                    <article class="flex flex-col min-h-screen w-full max-w-5xl min-w-0 bg-base items-start justify-center">
+                        <h1 class="mt-8">{post.title}</h1>
+                        <p class="my-2">{post.display_date()}</p>
+                        <TagChips tags=post.tags />
+                        {render_blocks(body)}
+                    </article>
+                </div>
+            }
+            .into_any())
+        })
+    };
+
+    view! {
+        <div class="flex md:flex-row flex-col min-h-screen w-full bg-base items-center justify-center overflow-x-auto">
+            <div class="md:w-2/3 w-5/6 flex flex-col justify-center items-center mb-4">
+                <Suspense fallback=move || view! { <p>"Loading blog..."</p> }>{blog_view}</Suspense>
+                <BlogFooter />
+            </div>
+        </div>
+    }
+}
+
+fn render_blocks(blocks: Vec<Block>) -> AnyView {
+    blocks
+        .into_iter()
+        .map(|block| match block {
+            Block::Heading {
+                level,
+                id,
+                children,
+            } => {
+                let children = render_inlines(children);
+                let links = view! { <HeadingLinks id=id.clone() /> };
+                match level {
+                    1 => view! {
+                        <h1 id=id class="group my-6 scroll-mt-4">{children}{links}</h1>
+                    }
+                    .into_any(),
+                    2 => view! {
+                        <h2 id=id class="group my-6 scroll-mt-4">{children}{links}</h2>
+                    }
+                    .into_any(),
+                    3 => view! {
+                        <h3 id=id class="group my-6 scroll-mt-4">{children}{links}</h3>
+                    }
+                    .into_any(),
+                    _ => view! {
+                        <h4 id=id class="group text-lg my-6 scroll-mt-4">{children}{links}</h4>
+                    }
+                    .into_any(),
+                }
+            }
+            Block::Paragraph(children) => {
+                view! { <p class="my-2">{render_inlines(children)}</p> }.into_any()
+            }
+            Block::Code {
+                lang,
+                code,
+                options,
+            } => view! {
+                <CodeBlock
+                    lang=lang
+                    code=code
+                    title=options.title
+                    line_numbers=options.line_numbers
+                    highlighted=options.highlighted
+                />
+            }
+            .into_any(),
+            Block::List { start, items } => {
+                let items = items
+                    .into_iter()
+                    .map(|item| view! { <li class="mt-2">{render_blocks(item)}</li> })
+                    .collect_view();
+                match start {
+                    Some(start) => view! {
+                        <ol class="list-decimal pl-6 w-full" start=start>
+                            {items}
+                        </ol>
+                    }
+                    .into_any(),
+                    None => view! { <ul class="list-disc pl-6 w-full">{items}</ul> }.into_any(),
+                }
+            }
+            Block::Quote(blocks) => view! {
+                <blockquote class="my-2 pl-4 border-l-2 border-peach">
+                    {render_blocks(blocks)}
+                </blockquote>
+            }
+            .into_any(),
+            Block::Rule => view! { <hr class="my-6 w-full border-surface" /> }.into_any(),
+        })
+        .collect_view()
+        .into_any()
+}
+
+fn render_inlines(inlines: Vec<Inline>) -> AnyView {
+    inlines
+        .into_iter()
+        .map(|inline| match inline {
+            Inline::Text(text) => text.into_any(),
+            Inline::Code(code) => view! { <code class="text-peach">{code}</code> }.into_any(),
+            Inline::Emphasis(children) => view! { <em>{render_inlines(children)}</em> }.into_any(),
+            Inline::Strong(children) => {
+                view! { <strong>{render_inlines(children)}</strong> }.into_any()
+            }
+            Inline::Strikethrough(children) => {
+                view! { <s>{render_inlines(children)}</s> }.into_any()
+            }
+            Inline::Link { href, children } => view! {
+                <a class="hover:text-maroon underline" href=href target="_blank">
+                    {render_inlines(children)}
+                </a>
+            }
+            .into_any(),
+            Inline::Image { src, alt } => view! { <Image src alt /> }.into_any(),
+            Inline::LineBreak => view! { <br /> }.into_any(),
+        })
+        .collect_view()
+        .into_any()
+}
@@ -1,2 +0,0 @@
-pub mod esp32_relay;
-pub mod nix_frame_hack;
@@ -0,0 +1,132 @@
+use leptos::prelude::*;
+use leptos_use::{use_clipboard_with_options, UseClipboardOptions, UseClipboardReturn};
+
+use crate::blog::highlight::{highlight, Highlight};
+
+/// A highlighted code block. Highlighting runs while rendering, so the server
+/// sends the finished markup and no script is needed.
+#[component]
+pub fn CodeBlock(
+    /// Language of `code`, e.g. `rust`. Unknown languages are shown plain.
+    #[prop(optional_no_strip)]
+    lang: Option<String>,
+    #[prop(into)] code: String,
+    /// Caption shown instead of the language, usually a file name.
+    #[prop(optional_no_strip)]
+    title: Option<String>,
+    #[prop(optional)] line_numbers: bool,
+    /// Inclusive, 1-based ranges of lines to mark.
+    #[prop(optional)]
+    highlighted: Vec<(usize, usize)>,
+) -> impl IntoView {
+    let lang = lang.unwrap_or_else(|| "plaintext".to_string());
+    let caption = title.unwrap_or_else(|| lang.clone());
+    let UseCopyReturn {
+        can_copy,
+        copied,
+        copy,
+    } = use_copy();
+
+    // Tokens can span lines, e.g. block comments, so split them per line.
+    let mut lines: Vec<Vec<(Option<Highlight>, String)>> = vec![Vec::new()];
+    for token in highlight(&lang, &code) {
+        for (i, part) in token.text.split('\n').enumerate() {
+            if i > 0 {
+                lines.push(Vec::new());
+            }
+            if !part.is_empty() {
+                lines
+                    .last_mut()
+                    .expect("starts with a line")
+                    .push((token.highlight, part.to_string()));
+            }
+        }
+    }
+
+    let lines = lines
+        .into_iter()
+        .enumerate()
+        .map(|(i, tokens)| {
+            let number = i + 1;
+            let marked = highlighted
+                .iter()
+                .any(|(start, end)| (*start..=*end).contains(&number));
+            let tokens = if tokens.is_empty() {
+                // Keeps empty lines from collapsing.
+                " ".into_any()
+            } else {
+                tokens
+                    .into_iter()
+                    .map(|(highlight, text)| match highlight {
+                        Some(highlight) => view! { <span class=highlight.class()>{text}</span> }
+                            .into_any(),
+                        None => text.into_any(),
+                    })
+                    .collect_view()
+                    .into_any()
+            };
+            view! {
+                <span class=if marked { "block -mx-4 px-4 bg-surface" } else { "block" }>
+                    {line_numbers
+                        .then(|| {
+                            view! {
+                                <span class="inline-block w-8 pr-4 text-right select-none opacity-50">
+                                    {number}
+                                </span>
+                            }
+                        })}
+                    {tokens}
+                </span>
+            }
+        })
+        .collect_view();
+
+    view! {
+        <figure class="code-block w-full">
+            <figcaption class="flex justify-between items-center py-1 text-xs border-b border-surface">
+                <span class="text-peach">{caption}</span>
+                <Show when=move || can_copy.get()>
+                    <button
+                        class="hover:text-maroon"
+                        on:click={
+                            let (code, copy) = (code.clone(), copy.clone());
+                            move |_| copy(&code)
+                        }
+                    >
+                        {move || if copied.get() { "Copied!" } else { "Copy" }}
+                    </button>
+                </Show>
+            </figcaption>
+            <pre>
+                <code class=format!("hljs language-{lang}")>{lines}</code>
+            </pre>
+        </figure>
+    }
+}
+
+/// What [`use_copy`] returns.
+pub struct UseCopyReturn<F: Fn(&str) + Clone> {
+    /// Whether a copy button should show, only ever `true` in the browser.
+    pub can_copy: ReadSignal<bool>,
+    /// `true` for two seconds after each copy.
+    pub copied: Signal<bool>,
+    pub copy: F,
+}
+
+/// The clipboard, for buttons that copy text.
+pub fn use_copy() -> UseCopyReturn<impl Fn(&str) + Clone + Send + Sync> {
+    let UseClipboardReturn {
+        is_supported,
+        copied,
+        copy,
+        ..
+    } = use_clipboard_with_options(UseClipboardOptions::default().copied_reset_delay(2000.0));
+    // Effects only run in the browser, so the button stays hidden without WASM.
+    let (can_copy, set_can_copy) = signal(false);
+    Effect::new(move |_| set_can_copy.set(is_supported.get()));
+    UseCopyReturn {
+        can_copy,
+        copied,
+        copy,
+    }
+}
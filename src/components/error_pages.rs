@@ -0,0 +1,217 @@
+use http::StatusCode;
+use leptos::logging::error;
+use leptos::prelude::*;
+use leptos_router::components::A;
+use leptos_router::hooks::use_location;
+
+use crate::blog::{use_visibility, POST_INDEX};
+use crate::components::seo::Seo;
+
+/// Pages that always exist, suggested next to posts and tags.
+const PAGES: &[&str] = &["/", "/blog", "/blog/tags", "/blog/search"];
+
+/// How many close matches a 404 suggests.
+const SUGGESTIONS: usize = 3;
+
+/// Shown for any path nothing lives at, with a 404 status and the closest
+/// existing pages.
+#[component]
+pub fn NotFound() -> impl IntoView {
+    set_status(StatusCode::NOT_FOUND);
+    let path = use_location().pathname.get_untracked();
+    let visibility = use_visibility();
+
+    let suggestions = {
+        let path = path.clone();
+        move || {
+            let path = path.clone();
+            Suspend::new(async move {
+                let visibility = visibility.await;
+                let mut candidates: Vec<String> =
+                    PAGES.iter().map(|page| page.to_string()).collect();
+                candidates.extend(POST_INDEX.visible(&visibility).map(|post| post.path()));
+                candidates.extend(
+                    POST_INDEX
+                        .tags(&visibility)
+                        .into_iter()
+                        .map(|(tag, _)| format!("/blog/tags/{tag}")),
+                );
+                let suggestions = closest(&path, candidates);
+                (!suggestions.is_empty()).then(|| {
+                    view! {
+                        <p class="mt-4">"Did you mean:"</p>
+                        <ul class="pl-4">
+                            {suggestions
+                                .into_iter()
+                                .map(|suggestion| {
+                                    view! {
+                                        <li>
+                                            <A href=suggestion.clone()>
+                                                <span class="text-blue hover:text-maroon">{suggestion}</span>
+                                            </A>
+                                        </li>
+                                    }
+                                })
+                                .collect_view()}
+                        </ul>
+                    }
+                })
+            })
+        }
+    };
+
+    view! {
+        <Seo title="Not found" description="There is nothing at this address." path=path.clone() noindex=true />
+        <TerminalWindow command=format!("cd {path}")>
+            <p class="text-maroon">{format!("bash: cd: {path}: No such file or directory")}</p>
+            <Suspense fallback=|| ()>{suggestions}</Suspense>
+        </TerminalWindow>
+    }
+}
+
+/// The fallback of the app's error boundary, for pages that failed to load,
+/// with a 500 status. The errors themselves are only logged.
+#[component]
+pub fn ErrorPage(errors: ArcRwSignal<Errors>) -> impl IntoView {
+    set_status(StatusCode::INTERNAL_SERVER_ERROR);
+    let path = use_location().pathname.get_untracked();
+    for (_, error) in errors.get_untracked() {
+        error!("rendering {path} failed: {error}");
+    }
+
+    view! {
+        <Seo title="Error" description="This page failed to load." path=path.clone() noindex=true />
+        <TerminalWindow command=format!("cat {path}")>
+            <p class="text-maroon">{format!("cat: {path}: Input/output error")}</p>
+            <p class="mt-4">"Something went wrong on our end, try again in a moment."</p>
+        </TerminalWindow>
+    }
+}
+
+/// A terminal that just ran `command`, with its output as children.
+#[component]
+fn TerminalWindow(command: String, children: Children) -> impl IntoView {
+    view! {
+        <div class="flex min-h-screen w-full bg-base items-center justify-center">
+            <div class="flex flex-col border shadow-md shadow-black border-peach rounded-md bg-base md:w-1/2 w-5/6 p-4 break-words">
+                <p>
+                    <span class="text-green">"visitor@rabbitnook"</span>
+                    ":"
+                    <span class="text-blue">"~"</span>
+                    {format!("$ {command}")}
+                </p>
+                {children()}
+                <A href="/">
+                    <p class="mt-4 hover:text-maroon">"cd ~"</p>
+                </A>
+            </div>
+        </div>
+    }
+}
+
+/// Sets the status of the response being rendered on the server.
+fn set_status(status: StatusCode) {
+    #[cfg(feature = "ssr")]
+    if let Some(response) = use_context::<leptos_axum::ResponseOptions>() {
+        response.set_status(status);
+    }
+    #[cfg(not(feature = "ssr"))]
+    let _ = status;
+}
+
+/// Up to [`SUGGESTIONS`] of `candidates` that look like `path`, closest first.
+fn closest(path: &str, candidates: Vec<String>) -> Vec<String> {
+    let path = path.trim_end_matches('/').to_lowercase();
+    let last = path.rsplit('/').next().unwrap_or_default();
+
+    let mut scored: Vec<(usize, String)> = candidates
+        .into_iter()
+        .filter_map(|candidate| {
+            let candidate_last = candidate.rsplit('/').next().unwrap_or_default();
+            let by_name = if last.is_empty() || candidate_last.is_empty() {
+                usize::MAX
+            } else if candidate_last.starts_with(last) || last.starts_with(candidate_last) {
+                1
+            } else {
+                distance(last, candidate_last)
+            };
+            let score = by_name.min(distance(&path, &candidate));
+            // Allows about one typo per three characters.
+            (score <= 2.max(candidate_last.len() / 3)).then_some((score, candidate))
+        })
+        .collect();
+    scored.sort();
+    scored
+        .into_iter()
+        .take(SUGGESTIONS)
+        .map(|(_, candidate)| candidate)
+        .collect()
+}
+
+/// Levenshtein distance between `a` and `b`, in characters.
+fn distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, a) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, b) in b.iter().enumerate() {
+            let above = row[j + 1];
+            row[j + 1] = (above + 1)
+                .min(row[j] + 1)
+                .min(diagonal + usize::from(a != *b));
+            diagonal = above;
+        }
+    }
+    row[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn suggest(path: &str, candidates: &[&str]) -> Vec<String> {
+        closest(path, candidates.iter().map(|c| c.to_string()).collect())
+    }
+
+    #[test]
+    fn levenshtein() {
+        assert_eq!(distance("", ""), 0);
+        assert_eq!(distance("", "abc"), 3);
+        assert_eq!(distance("abc", ""), 3);
+        assert_eq!(distance("blog", "blog"), 0);
+        assert_eq!(distance("blog", "blgo"), 2);
+        assert_eq!(distance("ab", "ba"), 2);
+        assert_eq!(distance("kitten", "sitting"), 3);
+        assert_eq!(distance("säfström", "safstrom"), 2);
+    }
+
+    #[test]
+    fn cuts_off_by_length() {
+        let candidates = ["/", "/projects", "/blog/nix-frame-hack"];
+        assert_eq!(suggest("/prjects", &candidates), ["/projects"]);
+        assert_eq!(suggest("/prjcts", &candidates), ["/projects"]);
+        assert!(suggest("/prjct", &candidates).is_empty());
+        // Longer names allow one typo per three characters.
+        assert_eq!(
+            suggest("/blog/nbx-frbme-hbcb", &candidates),
+            ["/blog/nix-frame-hack"]
+        );
+        assert!(suggest("/blog/bbx-frbme-hbcb", &candidates).is_empty());
+        assert!(suggest("/", &["/about"]).is_empty());
+    }
+
+    #[test]
+    fn closest_first() {
+        let candidates = ["/xyz", "/abce", "/abcdef", "/abcd", "/ab", "/blog/abcd"];
+        // The exact match, then the rest one edit or a prefix away, by path.
+        assert_eq!(suggest("/ABCD/", &candidates), ["/abcd", "/ab", "/abcdef"]);
+        assert_eq!(
+            suggest(
+                "/blog/esp32-rely",
+                &["/blog/esp32-relays", "/blog/esp32-relay"]
+            ),
+            ["/blog/esp32-relay", "/blog/esp32-relays"]
+        );
+    }
+}
@@ -0,0 +1,40 @@
+use leptos::prelude::*;
+
+use crate::blog::images::ResponsiveImage;
+
+/// Width the image takes up in a post, for the browser to pick a variant: the
+/// article is `md:w-2/3`, at most `max-w-5xl`, and `w-5/6` on phones.
+const SIZES: &str = "(min-width: 1536px) 64rem, (min-width: 768px) 66vw, 83vw";
+
+/// A lazily loaded post image. Images `build.rs` made variants of are served
+/// as AVIF or WebP in the size the screen needs, with their dimensions set
+/// and a blurry placeholder while they load. Others are shown as they are.
+#[component]
+pub fn Image(#[prop(into)] src: String, #[prop(into)] alt: String) -> impl IntoView {
+    let class = "my-6 rounded-md max-h-screen w-auto h-auto";
+    let Some(image) = ResponsiveImage::get(&src) else {
+        return view! { <img class=class src=src alt=alt loading="lazy" decoding="async" /> }
+            .into_any();
+    };
+
+    view! {
+        <picture>
+            <source type="image/avif" srcset=image.srcset("avif") sizes=SIZES />
+            <source type="image/webp" srcset=image.srcset("webp") sizes=SIZES />
+            <img
+                class=class
+                src=image.src
+                alt=alt
+                width=image.width
+                height=image.height
+                loading="lazy"
+                decoding="async"
+                style=format!(
+                    "background-image: url({}); background-size: cover",
+                    image.placeholder,
+                )
+            />
+        </picture>
+    }
+    .into_any()
+}
@@ -1,5 +1,12 @@
 pub mod blog;
-pub mod blogpages;
+pub mod blog_post;
+pub mod code_block;
+pub mod error_pages;
+pub mod image;
 pub mod links;
 pub mod name_header;
+pub mod search;
+pub mod seo;
+pub mod tags;
 pub mod terminal;
+pub mod toc;
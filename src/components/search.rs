@@ -0,0 +1,117 @@
+use leptos::prelude::*;
+use leptos_router::components::{Form, A};
+use leptos_router::hooks::use_query_map;
+
+use crate::blog::search::search_posts;
+use crate::blog::{use_visibility, POST_INDEX};
+use crate::components::blog::{BlogFooter, TagChips};
+use crate::components::seo::Seo;
+
+/// Searches the posts on `/blog/search`. A plain GET form, so it works
+/// before the page hydrates.
+#[component]
+pub fn SearchBox(#[prop(into, optional)] query: Signal<String>) -> impl IntoView {
+    view! {
+        <Form method="GET" action="/blog/search">
+            <div class="flex mt-4 gap-2">
+                <input
+                    class="px-2 bg-surface rounded-md"
+                    type="search"
+                    name="q"
+                    placeholder="Search posts"
+                    aria-label="Search posts"
+                    prop:value=query
+                />
+                <button class="hover:text-maroon" type="submit">
+                    "Search"
+                </button>
+            </div>
+        </Form>
+    }
+}
+
+/// Posts matching the `q` query parameter, with the matches highlighted.
+#[component]
+pub fn Search() -> impl IntoView {
+    let query = use_query_map();
+    let query = Signal::derive(move || query.read().get("q").unwrap_or_default());
+    let hits = Resource::new(
+        move || query.get(),
+        |query| async move {
+            // The bare page has nothing to look for.
+            if query.trim().is_empty() {
+                return Ok(Vec::new());
+            }
+            search_posts(query).await
+        },
+    );
+    let visibility = use_visibility();
+
+    view! {
+        {move || {
+            view! {
+                <Seo
+                    title=format!("Search: {}", query.get())
+                    description="Search the blog posts."
+                    path="/blog/search"
+                    noindex=true
+                />
+            }
+        }}
+        <div class="flex md:flex-row flex-col min-h-screen w-full bg-base items-center justify-center">
+            <div class="md:w-2/3 w-5/6 flex flex-col justify-center items-center">
+                <h1 class="mt-10 underline">Search</h1>
+                <SearchBox query />
+                <div class="flex flex-col items-center w-full mb-10">
+                    <Suspense fallback=|| ()>
+                    {move || Suspend::new(async move {
+                        let query = query.get();
+                        let hits = hits.await?;
+                        let visibility = visibility.await;
+                        if query.trim().is_empty() {
+                            return Ok(().into_any());
+                        }
+                        let hits: Vec<_> = hits
+                            .into_iter()
+                            .filter_map(|hit| Some((POST_INDEX.get(&hit.slug, &visibility)?, hit.snippet)))
+                            .collect();
+                        if hits.is_empty() {
+                            return Ok(view! { <p class="mt-10">{format!("No posts match \"{query}\".")}</p> }
+                                .into_any());
+                        }
+                        Ok::<_, ServerFnError>(hits.into_iter()
+                            .map(|(post, snippet)| {
+                                view! {
+                                    <div class="flex flex-col mt-10 border rounded-md w-full">
+                                        <A href=post.path()>
+                                            <h1 class="mx-4 mt-2 text-xl hover:text-maroon">{post.title}</h1>
+                                        </A>
+                                        <p class="mx-4 mt-2">{post.display_date()}</p>
+                                        <p class="mx-4 my-2">
+                                            {snippet
+                                                .into_iter()
+                                                .map(|(text, matched)| {
+                                                    if matched {
+                                                        view! { <mark class="bg-surface text-maroon">{text}</mark> }.into_any()
+                                                    } else {
+                                                        text.into_any()
+                                                    }
+                                                })
+                                                .collect_view()}
+                                        </p>
+                                        <div class="mx-4 mb-2">
+                                            <TagChips tags=post.tags />
+                                        </div>
+                                    </div>
+                                }
+                            })
+                            .collect_view()
+                            .into_any())
+                    })}
+                    </Suspense>
+                </div>
+                <BlogFooter />
+            </div>
+        </div>
+    }
+}
@@ -0,0 +1,138 @@
+use leptos::html::inner_html;
+use leptos::prelude::*;
+use leptos_meta::{Link, Meta, Script, Title};
+use serde_json::json;
+
+use crate::blog::Post;
+use crate::site::{use_site_origin, AUTHOR, SITE_NAME};
+
+/// Shown in link previews of pages other than posts.
+const DEFAULT_IMAGE: &str = "/portrait.png";
+/// Size of the cards posts get from `og_image`.
+const CARD_SIZE: (&str, &str) = ("1200", "630");
+
+/// Title, description, canonical URL and link preview tags for a page. With a
+/// `post`, the page is described as an article and gets `BlogPosting`
+/// structured data.
+#[component]
+pub fn Seo(
+    /// The page's own title, the site name is added to it.
+    #[prop(into)]
+    title: String,
+    #[prop(into)] description: String,
+    /// Path of the page, e.g. `/blog`.
+    #[prop(into)]
+    path: String,
+    #[prop(optional)] post: Option<&'static Post>,
+    /// Keeps search engines from indexing the page, e.g. search results.
+    #[prop(optional)]
+    noindex: bool,
+) -> impl IntoView {
+    let origin = use_site_origin();
+    let title = if title == SITE_NAME {
+        title
+    } else {
+        format!("{title} | {SITE_NAME}")
+    };
+
+    let tags = {
+        let (title, description) = (title.clone(), description.clone());
+        move || {
+            let (title, description, path) = (title.clone(), description.clone(), path.clone());
+            Suspend::new(async move {
+                let origin = origin.await;
+                let url = format!("{origin}{path}");
+                let image = match post {
+                    Some(post) => format!("{origin}{}", post.og_image_path()),
+                    None => format!("{origin}{DEFAULT_IMAGE}"),
+                };
+                view! {
+                    <Link rel="canonical" href=url.clone() />
+                    <Meta property="og:site_name" content=SITE_NAME />
+                    <Meta property="og:title" content=title.clone() />
+                    <Meta property="og:description" content=description.clone() />
+                    <Meta property="og:url" content=url.clone() />
+                    <Meta property="og:image" content=image.clone() />
+                    {post
+                        .map(|_| {
+                            view! {
+                                <Meta property="og:image:width" content=CARD_SIZE.0 />
+                                <Meta property="og:image:height" content=CARD_SIZE.1 />
+                            }
+                        })}
+                    <Meta property="og:type" content=if post.is_some() { "article" } else { "website" } />
+                    <Meta
+                        name="twitter:card"
+                        content=if post.is_some() { "summary_large_image" } else { "summary" }
+                    />
+                    <Meta name="twitter:title" content=title />
+                    <Meta name="twitter:description" content=description />
+                    <Meta name="twitter:image" content=image.clone() />
+                    {post.map(|post| article(post, &url, &image))}
+                }
+            })
+        }
+    };
+
+    view! {
+        <Title text=title />
+        <Meta name="description" content=description />
+        {noindex.then(|| view! { <Meta name="robots" content="noindex" /> })}
+        <Suspense fallback=|| ()>{tags}</Suspense>
+    }
+}
+
+/// Open Graph article tags and JSON-LD for a post at `url`.
+fn article(post: &'static Post, url: &str, image: &str) -> AnyView {
+    let json_ld = json!({
+        "@context": "https://schema.org",
+        "@type": "BlogPosting",
+        "headline": post.title,
+        "description": post.summary,
+        "datePublished": post.date,
+        "dateModified": post.date,
+        "keywords": post.tags.join(", "),
+        "url": url,
+        "mainEntityOfPage": url,
+        "image": image,
+        "author": { "@type": "Person", "name": AUTHOR },
+        "publisher": { "@type": "Organization", "name": SITE_NAME },
+    });
+
+    view! {
+        <Meta property="article:published_time" content=post.date />
+        <Meta property="article:author" content=AUTHOR />
+        {post
+            .tags
+            .iter()
+            .map(|tag| view! { <Meta property="article:tag" content=*tag /> })
+            .collect_view()}
+        <Script type_="application/ld+json" {..inner_html(script_json(&json_ld))} />
+    }
+    .into_any()
+}
+
+/// `value` as JSON that is safe inside a `<script>`: `<`, `>` and `&` are
+/// written as escapes, so a `</script>` or `<!--` in a title can't end or
+/// change the element.
+fn script_json(value: &serde_json::Value) -> String {
+    value
+        .to_string()
+        .replace('<', "\\u003c")
+        .replace('>', "\\u003e")
+        .replace('&', "\\u0026")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn script_json_escapes_markup() {
+        let value = json!({ "headline": "</script><!-- a & b -->" });
+        assert_eq!(
+            script_json(&value),
+            r#"{"headline":"\u003c/script\u003e\u003c!-- a \u0026 b --\u003e"}"#
+        );
+    }
+}
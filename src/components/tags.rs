@@ -0,0 +1,87 @@
+use leptos::prelude::*;
+use leptos_router::components::A;
+use leptos_router::hooks::use_params_map;
+
+use crate::blog::{use_visibility, POST_INDEX};
+use crate::components::blog::{BlogFooter, PostCard};
+use crate::components::error_pages::NotFound;
+use crate::components::seo::Seo;
+
+/// Every tag and how many posts have it.
+#[component]
+pub fn Tags() -> impl IntoView {
+    let visibility = use_visibility();
+    let tags = move || {
+        Suspend::new(async move {
+            let visibility = visibility.await;
+            POST_INDEX
+                .tags(&visibility)
+                .into_iter()
+                .map(|(tag, count)| {
+                    view! {
+                        <li class="px-2 border border-peach rounded-md hover:text-maroon">
+                            <A href=format!("/blog/tags/{tag}")>{format!("#{tag} ({count})")}</A>
+                        </li>
+                    }
+                })
+                .collect_view()
+        })
+    };
+
+    view! {
+        <Seo title="Tags" description="Every tag used on the blog." path="/blog/tags" />
+        <div class="flex md:flex-row flex-col min-h-screen w-full bg-base items-center justify-center">
+            <div class="md:w-2/3 w-5/6 flex flex-col justify-center items-center">
+                <h1 class="my-10 underline">Tags</h1>
+                <ul class="flex flex-wrap justify-center gap-4 mb-10">
+                    <Suspense fallback=|| ()>{tags}</Suspense>
+                </ul>
+                <BlogFooter />
+            </div>
+        </div>
+    }
+}
+
+/// Posts with the tag in the `:tag` route parameter. A tag no visible post
+/// has is a 404.
+#[component]
+pub fn TagPosts() -> impl IntoView {
+    let params = use_params_map();
+    let tag = move || params.read().get("tag").unwrap_or_default();
+    let visibility = use_visibility();
+    let page = move || {
+        Suspend::new(async move {
+            let visibility = visibility.await;
+            let tag = tag();
+            let posts: Vec<_> = POST_INDEX.tagged(&tag, &visibility).collect();
+            if posts.is_empty() {
+                return view! { <NotFound /> }.into_any();
+            }
+            view! {
+                <Seo
+                    title=format!("#{tag}")
+                    description=format!("Posts tagged #{tag}.")
+                    path=format!("/blog/tags/{tag}")
+                />
+                <div class="flex md:flex-row flex-col min-h-screen w-full bg-base items-center justify-center">
+                    <div class="md:w-2/3 w-5/6 flex flex-col justify-center items-center">
+                        <h1 class="mt-10 underline">{format!("Posts tagged #{tag}")}</h1>
+                        <A href="/blog/tags">
+                            <p class="mt-2 hover:text-maroon">All tags</p>
+                        </A>
+                        <div class="flex flex-col items-center mb-10">
+                            {posts
+                                .into_iter()
+                                .map(|post| view! { <PostCard post=post /> })
+                                .collect_view()}
+                        </div>
+                        <BlogFooter />
+                    </div>
+                </div>
+            }
+            .into_any()
+        })
+    };
+
+    view! { <Suspense fallback=|| ()>{page}</Suspense> }
+}
@@ -0,0 +1,647 @@
+use lazy_static::lazy_static;
+
+use super::filters::{Grep, Head, Sort, Wc};
+use super::fs::{self, Node};
+use super::output::{Block, Color, Span};
+use super::parser::{Args, CommandError};
+use super::registry::{CommandContext, CommandRegistry, Output, ShellCommand};
+#[cfg(feature = "ssr")]
+use crate::blog::search;
+use crate::blog::search::Hit;
+use crate::blog::{Post, Visibility, POST_INDEX};
+
+lazy_static! {
+    /// Every command the home-page terminal knows about.
+    pub static ref COMMANDS: CommandRegistry = builtin();
+}
+
+/// Commands that just print a link and open it in a new tab.
+const LINKS: &[LinkCommand] = &[
+    LinkCommand {
+        name: "source",
+        usage: "source",
+        aliases: &["repo"],
+        description: "Show the source of this site",
+        url: "https://github.com/safstromo/rabbitnook",
+        label: "github.com/safstromo/rabbitnook",
+        accepts_path: false,
+    },
+    LinkCommand {
+        name: "git",
+        usage: "git [repo]",
+        aliases: &["github"],
+        description: "Open my GitHub profile",
+        url: "https://github.com/safstromo",
+        label: "github.com/safstromo",
+        accepts_path: true,
+    },
+    LinkCommand {
+        name: "vim",
+        usage: "vim",
+        aliases: &["nvim"],
+        description: "Open my neovim config",
+        url: "https://github.com/safstromo/.dotfiles/tree/main/nvim/.config/nvim",
+        label: "nvim .dotfiles",
+        accepts_path: false,
+    },
+    LinkCommand {
+        name: "email",
+        usage: "email",
+        aliases: &["mail"],
+        description: "Send me an email",
+        url: "mailto:safstrom.oliver@gmail.com",
+        label: "safstrom.oliver@gmail.com",
+        accepts_path: false,
+    },
+    LinkCommand {
+        name: "sudo",
+        usage: "sudo",
+        aliases: &[],
+        description: "Run a command as root",
+        url: "https://www.youtube.com/watch?v=dQw4w9WgXcQ",
+        label: "sudo",
+        accepts_path: false,
+    },
+    LinkCommand {
+        name: "linkedin",
+        usage: "linkedin",
+        aliases: &[],
+        description: "Open my LinkedIn profile",
+        url: "https://www.linkedin.com/in/safstromo/",
+        label: "linkedin.com/in/safstromo",
+        accepts_path: false,
+    },
+];
+
+fn builtin() -> CommandRegistry {
+    let mut registry = CommandRegistry::default();
+    registry.register(Help);
+    for link in LINKS {
+        registry.register(*link);
+    }
+    registry.register(Blog);
+    registry.register(Search);
+    registry.register(Pwd);
+    registry.register(Ls);
+    registry.register(Cd);
+    registry.register(Cat);
+    registry.register(Tree);
+    registry.register(Echo);
+    registry.register(Grep);
+    registry.register(Head);
+    registry.register(Wc);
+    registry.register(Sort);
+    registry.register(Neofetch);
+    registry.register(Clear);
+    registry
+}
+
+struct Help;
+
+impl ShellCommand for Help {
+    fn name(&self) -> &'static str {
+        "help"
+    }
+
+    fn description(&self) -> &'static str {
+        "List available commands"
+    }
+
+    fn usage(&self) -> &'static str {
+        "help [command]"
+    }
+
+    fn execute(&self, args: &Args, ctx: &CommandContext) -> Result<Output, CommandError> {
+        args.at_most(1)?;
+        if let Some(name) = args.first() {
+            let command = ctx
+                .registry
+                .find(name)
+                .ok_or_else(|| CommandError::Failed(format!("no such command: {name}")))?;
+            return Ok(Output::Print(vec![Block::Line(vec![
+                Span::colored(command.usage(), Color::Green).bold(),
+                Span::plain(format!(" - {}", command.description())),
+            ])]));
+        }
+
+        let rows = ctx
+            .registry
+            .iter()
+            .map(|command| {
+                vec![
+                    Span::colored(command.name(), Color::Green).bold(),
+                    Span::plain(command.description()),
+                ]
+            })
+            .collect();
+        Ok(Output::Print(vec![
+            Block::Text("Available commands:".to_string()),
+            Block::Table(rows),
+            Block::Text("Run 'help <command>' or '<command> --help' for usage.".to_string()),
+        ]))
+    }
+
+    fn complete(&self, args: &[String], _partial: &str, ctx: &CommandContext) -> Vec<String> {
+        if !args.is_empty() {
+            return Vec::new();
+        }
+        ctx.registry
+            .iter()
+            .map(|command| command.name().to_string())
+            .collect()
+    }
+}
+
+struct Clear;
+
+impl ShellCommand for Clear {
+    fn name(&self) -> &'static str {
+        "clear"
+    }
+
+    fn description(&self) -> &'static str {
+        "Clear the terminal"
+    }
+
+    fn execute(&self, args: &Args, _ctx: &CommandContext) -> Result<Output, CommandError> {
+        args.at_most(0)?;
+        Ok(Output::Clear)
+    }
+}
+
+struct Echo;
+
+impl ShellCommand for Echo {
+    fn name(&self) -> &'static str {
+        "echo"
+    }
+
+    fn description(&self) -> &'static str {
+        "Print the given text"
+    }
+
+    fn usage(&self) -> &'static str {
+        "echo [text...]"
+    }
+
+    fn execute(&self, args: &Args, _ctx: &CommandContext) -> Result<Output, CommandError> {
+        args.allow_flags(&[])?;
+        Ok(Output::text(args.positional().join(" ")))
+    }
+}
+
+#[derive(Clone, Copy)]
+struct LinkCommand {
+    name: &'static str,
+    usage: &'static str,
+    aliases: &'static [&'static str],
+    description: &'static str,
+    url: &'static str,
+    label: &'static str,
+    /// Whether an optional path argument is appended to `url`.
+    accepts_path: bool,
+}
+
+impl ShellCommand for LinkCommand {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        self.aliases
+    }
+
+    fn description(&self) -> &'static str {
+        self.description
+    }
+
+    fn usage(&self) -> &'static str {
+        self.usage
+    }
+
+    fn execute(&self, args: &Args, _ctx: &CommandContext) -> Result<Output, CommandError> {
+        args.at_most(if self.accepts_path { 1 } else { 0 })?;
+        match args.first() {
+            Some(path) => Ok(Output::Link {
+                url: format!("{}/{path}", self.url),
+                name: format!("{}/{path}", self.label),
+            }),
+            None => Ok(Output::Link {
+                url: self.url.to_string(),
+                name: self.label.to_string(),
+            }),
+        }
+    }
+}
+
+struct Search;
+
+impl ShellCommand for Search {
+    fn name(&self) -> &'static str {
+        "search"
+    }
+
+    fn description(&self) -> &'static str {
+        "Search the blog posts"
+    }
+
+    fn usage(&self) -> &'static str {
+        "search <query...>"
+    }
+
+    fn on_server(&self) -> bool {
+        true
+    }
+
+    fn execute(&self, args: &Args, ctx: &CommandContext) -> Result<Output, CommandError> {
+        args.allow_flags(&[])?;
+        if args.positional().is_empty() {
+            return Err(CommandError::Usage);
+        }
+        let query = args.positional().join(" ");
+        let visibility = &ctx.env.visibility;
+        let hits = search_index(&query, visibility)?;
+        if hits.is_empty() {
+            return Err(CommandError::Failed(format!("no posts match '{query}'")));
+        }
+
+        let mut blocks = Vec::new();
+        for hit in hits {
+            let Some(post) = POST_INDEX.get(&hit.slug, visibility) else {
+                continue;
+            };
+            blocks.push(Block::Line(vec![
+                Span::colored(post.slug, Color::Green).bold(),
+                Span::plain(format!("  {}", post.title)),
+            ]));
+            blocks.push(Block::Line(
+                hit.snippet
+                    .into_iter()
+                    .map(|(text, matched)| match matched {
+                        true => Span::colored(text, Color::Maroon).bold(),
+                        false => Span::plain(text),
+                    })
+                    .collect(),
+            ));
+        }
+        blocks.push(Block::Text(
+            "Run 'blog open <slug>' to read a post.".to_string(),
+        ));
+        Ok(Output::Print(blocks))
+    }
+}
+
+#[cfg(feature = "ssr")]
+fn search_index(query: &str, visibility: &Visibility) -> Result<Vec<Hit>, CommandError> {
+    Ok(search::search(query, visibility))
+}
+
+/// Only the server has the index, which is why `search` runs there.
+#[cfg(not(feature = "ssr"))]
+fn search_index(_query: &str, _visibility: &Visibility) -> Result<Vec<Hit>, CommandError> {
+    Err(CommandError::Failed(
+        "the search index is on the server".to_string(),
+    ))
+}
+
+struct Pwd;
+
+impl ShellCommand for Pwd {
+    fn name(&self) -> &'static str {
+        "pwd"
+    }
+
+    fn description(&self) -> &'static str {
+        "Print the working directory"
+    }
+
+    fn execute(&self, args: &Args, ctx: &CommandContext) -> Result<Output, CommandError> {
+        args.at_most(0)?;
+        Ok(Output::text(ctx.cwd))
+    }
+}
+
+struct Ls;
+
+impl ShellCommand for Ls {
+    fn name(&self) -> &'static str {
+        "ls"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["dir"]
+    }
+
+    fn description(&self) -> &'static str {
+        "List directory contents"
+    }
+
+    fn usage(&self) -> &'static str {
+        "ls [-l] [path]"
+    }
+
+    fn flags(&self) -> &'static [(char, &'static str)] {
+        &[('l', "long")]
+    }
+
+    fn execute(&self, args: &Args, ctx: &CommandContext) -> Result<Output, CommandError> {
+        args.allow_flags(self.flags())?;
+        if args.positional().len() > 1 {
+            return Err(CommandError::Usage);
+        }
+        let path = args.first().unwrap_or(".");
+        let root = fs::root(ctx.env);
+        let entries: Vec<(&str, &Node)> = match find(&root, ctx, path)? {
+            Node::Dir(entries) => entries
+                .iter()
+                .map(|(name, node)| (name.as_str(), node))
+                .collect(),
+            file => vec![(path, file)],
+        };
+
+        if args.has_flag('l', "long") {
+            let rows = entries
+                .into_iter()
+                .map(|(name, node)| match node {
+                    Node::Dir(children) => vec![
+                        Span::plain("dr-xr-xr-x"),
+                        Span::plain(children.len().to_string()),
+                        Span::colored(format!("{name}/"), Color::Blue).bold(),
+                    ],
+                    Node::File(contents) => vec![
+                        Span::plain("-r--r--r--"),
+                        Span::plain(contents.len().to_string()),
+                        Span::plain(name),
+                    ],
+                })
+                .collect();
+            return Ok(Output::Print(vec![Block::Table(rows)]));
+        }
+
+        let spans = entries
+            .into_iter()
+            .enumerate()
+            .flat_map(|(i, (name, node))| {
+                let separator = (i > 0).then(|| Span::plain("  "));
+                let entry = match node {
+                    Node::Dir(_) => Span::colored(format!("{name}/"), Color::Blue).bold(),
+                    Node::File(_) => Span::plain(name),
+                };
+                separator.into_iter().chain([entry])
+            })
+            .collect();
+        Ok(Output::Print(vec![Block::Line(spans)]))
+    }
+
+    fn complete(&self, _args: &[String], partial: &str, ctx: &CommandContext) -> Vec<String> {
+        fs::complete_path(ctx, partial, false)
+    }
+}
+
+struct Cd;
+
+impl ShellCommand for Cd {
+    fn name(&self) -> &'static str {
+        "cd"
+    }
+
+    fn description(&self) -> &'static str {
+        "Change the working directory"
+    }
+
+    fn usage(&self) -> &'static str {
+        "cd [dir]"
+    }
+
+    fn execute(&self, args: &Args, ctx: &CommandContext) -> Result<Output, CommandError> {
+        args.at_most(1)?;
+        let path = args.first().unwrap_or("~");
+        match find(&fs::root(ctx.env), ctx, path)? {
+            Node::Dir(_) => Ok(Output::ChangeDir(fs::resolve(ctx.cwd, path))),
+            Node::File(_) => Err(CommandError::Failed(format!("{path}: Not a directory"))),
+        }
+    }
+
+    fn complete(&self, args: &[String], partial: &str, ctx: &CommandContext) -> Vec<String> {
+        if !args.is_empty() {
+            return Vec::new();
+        }
+        fs::complete_path(ctx, partial, true)
+    }
+}
+
+struct Cat;
+
+impl ShellCommand for Cat {
+    fn name(&self) -> &'static str {
+        "cat"
+    }
+
+    fn description(&self) -> &'static str {
+        "Print the contents of files"
+    }
+
+    fn usage(&self) -> &'static str {
+        "cat [file...]"
+    }
+
+    fn execute(&self, args: &Args, ctx: &CommandContext) -> Result<Output, CommandError> {
+        args.allow_flags(&[])?;
+        Ok(Output::text(read_input(args.positional(), ctx)?))
+    }
+
+    fn complete(&self, _args: &[String], partial: &str, ctx: &CommandContext) -> Vec<String> {
+        fs::complete_path(ctx, partial, false)
+    }
+}
+
+struct Tree;
+
+impl ShellCommand for Tree {
+    fn name(&self) -> &'static str {
+        "tree"
+    }
+
+    fn description(&self) -> &'static str {
+        "Show the directory tree"
+    }
+
+    fn usage(&self) -> &'static str {
+        "tree [dir]"
+    }
+
+    fn execute(&self, args: &Args, ctx: &CommandContext) -> Result<Output, CommandError> {
+        args.at_most(1)?;
+        let path = args.first().unwrap_or(".");
+        Ok(Output::Print(vec![Block::Pre(vec![Span::plain(
+            fs::tree(path, find(&fs::root(ctx.env), ctx, path)?),
+        )])]))
+    }
+
+    fn complete(&self, args: &[String], partial: &str, ctx: &CommandContext) -> Vec<String> {
+        if !args.is_empty() {
+            return Vec::new();
+        }
+        fs::complete_path(ctx, partial, true)
+    }
+}
+
+/// The contents of `paths` joined, or the piped input when there are none.
+pub(super) fn read_input(paths: &[String], ctx: &CommandContext) -> Result<String, CommandError> {
+    if paths.is_empty() {
+        return ctx.stdin.map(str::to_string).ok_or(CommandError::Usage);
+    }
+
+    let root = fs::root(ctx.env);
+    let mut contents = Vec::new();
+    for path in paths {
+        match find(&root, ctx, path)? {
+            Node::File(text) => contents.push(text.as_str()),
+            Node::Dir(_) => {
+                return Err(CommandError::Failed(format!("{path}: Is a directory")));
+            }
+        }
+    }
+    Ok(contents.join("\n"))
+}
+
+/// Resolves `path` against the working directory or fails like coreutils do.
+fn find<'a>(root: &'a Node, ctx: &CommandContext, path: &str) -> Result<&'a Node, CommandError> {
+    fs::lookup(root, &fs::resolve(ctx.cwd, path))
+        .ok_or_else(|| CommandError::Failed(format!("{path}: No such file or directory")))
+}
+
+struct Blog;
+
+impl Blog {
+    fn open(post: &Post) -> Output {
+        Output::Navigate {
+            path: post.path(),
+            message: format!("Opening {}...", post.title),
+        }
+    }
+}
+
+impl ShellCommand for Blog {
+    fn name(&self) -> &'static str {
+        "blog"
+    }
+
+    fn description(&self) -> &'static str {
+        "Read the blog, or list and open posts"
+    }
+
+    fn usage(&self) -> &'static str {
+        "blog [ls | latest | open <slug>]"
+    }
+
+    fn execute(&self, args: &Args, ctx: &CommandContext) -> Result<Output, CommandError> {
+        args.at_most(2)?;
+        let visibility = &ctx.env.visibility;
+        match args.positional() {
+            [] => Ok(Output::Navigate {
+                path: "/blog".to_string(),
+                message: "Opening blog...".to_string(),
+            }),
+            [sub] if sub == "ls" => Ok(Output::Print(vec![Block::Table(
+                POST_INDEX
+                    .visible(visibility)
+                    .map(|post| {
+                        vec![
+                            Span::colored(post.date, Color::Peach),
+                            Span::colored(post.slug, Color::Green).bold(),
+                            Span::plain(post.title),
+                        ]
+                    })
+                    .collect(),
+            )])),
+            [sub] if sub == "latest" => POST_INDEX
+                .latest(visibility)
+                .map(Blog::open)
+                .ok_or_else(|| CommandError::Failed("no posts yet".to_string())),
+            [sub, slug] if sub == "open" => POST_INDEX
+                .get(slug, visibility)
+                .map(Blog::open)
+                .ok_or_else(|| CommandError::Failed(format!("{slug}: no such post"))),
+            _ => Err(CommandError::Usage),
+        }
+    }
+
+    fn complete(&self, args: &[String], _partial: &str, ctx: &CommandContext) -> Vec<String> {
+        match args {
+            [] => ["ls", "latest", "open"].map(String::from).to_vec(),
+            [sub] if sub == "open" => POST_INDEX
+                .visible(&ctx.env.visibility)
+                .map(|post| post.slug.to_string())
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+struct Neofetch;
+
+/// `origin` without its scheme.
+fn host(origin: &str) -> &str {
+    origin.split_once("://").map_or(origin, |(_, host)| host)
+}
+
+/// The rabbit shown next to the system info.
+const RABBIT: &[&str] = &[
+    r#"   (\(\       "#,
+    r#"   ( -.-)     "#,
+    r#"  o_(")(")    "#,
+];
+
+impl ShellCommand for Neofetch {
+    fn name(&self) -> &'static str {
+        "neofetch"
+    }
+
+    fn description(&self) -> &'static str {
+        "Show system information"
+    }
+
+    fn execute(&self, args: &Args, ctx: &CommandContext) -> Result<Output, CommandError> {
+        args.at_most(0)?;
+        let info = [
+            ("", "visitor@rabbitnook".to_string()),
+            ("OS", "RabbitNook v1.0.0".to_string()),
+            ("Host", host(&ctx.env.origin).to_string()),
+            ("Kernel", "Leptos + Axum".to_string()),
+            ("Shell", "rbsh".to_string()),
+            ("Commands", ctx.registry.iter().count().to_string()),
+            (
+                "Posts",
+                POST_INDEX.visible(&ctx.env.visibility).count().to_string(),
+            ),
+            ("Theme", "Catppuccin".to_string()),
+        ];
+
+        let mut spans = Vec::new();
+        for (i, (key, value)) in info.into_iter().enumerate() {
+            let art = RABBIT.get(i).copied().unwrap_or("              ");
+            spans.push(Span::colored(art, Color::Pink));
+            if key.is_empty() {
+                spans.push(Span::colored(value, Color::Teal).bold());
+            } else {
+                spans.push(Span::colored(format!("{key}: "), Color::Blue).bold());
+                spans.push(Span::plain(value));
+            }
+            spans.push(Span::plain("\n"));
+        }
+        let palette = [
+            Color::Sky,
+            Color::Blue,
+            Color::Maroon,
+            Color::Peach,
+            Color::Green,
+            Color::Teal,
+            Color::Pink,
+        ];
+        spans.push(Span::plain("              "));
+        spans.extend(palette.map(|color| Span::colored("███", color)));
+
+        Ok(Output::Print(vec![Block::Pre(spans)]))
+    }
+}
@@ -0,0 +1,178 @@
+use super::registry::{CommandContext, CommandRegistry, Environment};
+
+/// Result of pressing Tab on a partially typed line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Completion {
+    /// Nothing matched, leave the line alone.
+    None,
+    /// The line with the current word completed or extended.
+    Line(String),
+    /// Several candidates share no further common prefix; show them.
+    Candidates(Vec<String>),
+}
+
+/// Completes the last word of `line`, either as a command name or as an
+/// argument of the command it starts with. Words starting with `-` complete
+/// to its flags. Only the command after the last `|`, `&&`, `||` or `;` is
+/// looked at, like [`parse`](super::parser::parse) splits them.
+pub fn complete(
+    registry: &CommandRegistry,
+    line: &str,
+    cwd: &str,
+    env: &Environment,
+) -> Completion {
+    // A lone `&` is plain text, as in the parser.
+    let start = line
+        .match_indices(['|', ';'])
+        .map(|(i, _)| i + 1)
+        .chain(line.match_indices("&&").map(|(i, _)| i + 2))
+        .max()
+        .unwrap_or(0);
+    let command = &line[start..];
+    let words: Vec<&str> = command.split_whitespace().collect();
+    // A trailing space means the next word has not been started yet.
+    let (done, partial) = match words.split_last() {
+        Some((last, rest)) if !command.ends_with(char::is_whitespace) => (rest, *last),
+        _ => (words.as_slice(), ""),
+    };
+
+    let mut candidates: Vec<String> = match done.split_first() {
+        None => registry
+            .iter()
+            .map(|command| command.name().to_string())
+            .collect(),
+        Some((name, args)) => match registry.find(name) {
+            Some(command) if partial.starts_with('-') => command
+                .flags()
+                .iter()
+                .chain(command.options())
+                .chain(&[('h', "help")])
+                .flat_map(|(short, long)| [format!("-{short}"), format!("--{long}")])
+                .collect(),
+            Some(command) => {
+                let args: Vec<String> = args.iter().map(|arg| arg.to_string()).collect();
+                command.complete(
+                    &args,
+                    partial,
+                    &CommandContext {
+                        registry,
+                        env,
+                        cwd,
+                        stdin: None,
+                    },
+                )
+            }
+            None => Vec::new(),
+        },
+    };
+    candidates.retain(|candidate| candidate.starts_with(partial));
+    candidates.sort();
+    candidates.dedup();
+
+    let head = &line[..line.len() - partial.len()];
+    match candidates.as_slice() {
+        [] => Completion::None,
+        [only] => {
+            // Directories keep the cursor inside the path so it can be continued.
+            let separator = if only.ends_with('/') { "" } else { " " };
+            Completion::Line(format!("{head}{only}{separator}"))
+        }
+        [first, rest @ ..] => {
+            let common = rest.iter().fold(first.as_str(), |common, candidate| {
+                let len = common
+                    .char_indices()
+                    .zip(candidate.chars())
+                    .take_while(|((_, a), b)| a == b)
+                    .last()
+                    .map_or(0, |((i, a), _)| i + a.len_utf8());
+                &common[..len]
+            });
+            if common.len() > partial.len() {
+                Completion::Line(format!("{head}{common}"))
+            } else {
+                Completion::Candidates(candidates)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::terminal::commands::COMMANDS;
+
+    fn complete_in(cwd: &str, line: &str) -> Completion {
+        let env = Environment {
+            visibility: serde_json::from_str(r#"{"today":"2025-06-01","preview":false}"#).unwrap(),
+            origin: "https://example.com".to_string(),
+        };
+        complete(&COMMANDS, line, cwd, &env)
+    }
+
+    fn tab(line: &str) -> Completion {
+        complete_in("/", line)
+    }
+
+    fn line(line: &str) -> Completion {
+        Completion::Line(line.to_string())
+    }
+
+    fn candidates(candidates: &[&str]) -> Completion {
+        Completion::Candidates(candidates.iter().map(|c| c.to_string()).collect())
+    }
+
+    #[test]
+    fn command_names() {
+        assert_eq!(tab("ec"), line("echo "));
+        assert_eq!(tab("he"), candidates(&["head", "help"]));
+        assert_eq!(tab("hel"), line("help "));
+        assert_eq!(tab("xyz"), Completion::None);
+        assert_eq!(tab("help gr"), line("help grep "));
+    }
+
+    #[test]
+    fn flags() {
+        assert_eq!(tab("grep --ig"), line("grep --ignore-case "));
+        assert_eq!(
+            tab("grep --i"),
+            candidates(&["--ignore-case", "--invert-match"])
+        );
+        assert_eq!(tab("head --l"), line("head --lines "));
+        assert_eq!(
+            tab("wc -"),
+            candidates(&["--bytes", "--help", "--lines", "--words", "-c", "-h", "-l", "-w"])
+        );
+        assert_eq!(tab("cd -"), candidates(&["--help", "-h"]));
+    }
+
+    #[test]
+    fn paths() {
+        assert_eq!(tab("cd pro"), line("cd projects/"));
+        assert_eq!(tab("cd blog/../pro"), line("cd blog/../projects/"));
+        // `/blog` holds only files, which `cd` does not offer.
+        assert_eq!(tab("cd blog/"), Completion::None);
+        assert_eq!(
+            tab("cat blog/"),
+            candidates(&["blog/esp32-relay.md", "blog/nix-frame-hack.md"])
+        );
+        assert_eq!(tab("cat blog/n"), line("cat blog/nix-frame-hack.md "));
+        assert_eq!(complete_in("/blog", "cat e"), line("cat esp32-relay.md "));
+        assert_eq!(tab("cat ~/con"), line("cat ~/contact.txt "));
+    }
+
+    #[test]
+    fn after_operators() {
+        assert_eq!(tab("cat about.txt | gr"), line("cat about.txt | grep "));
+        assert_eq!(tab("ls && ec"), line("ls && echo "));
+        assert_eq!(tab("ls || ec"), line("ls || echo "));
+        assert_eq!(tab("ls; cd pro"), line("ls; cd projects/"));
+        assert_eq!(tab("ls | "), tab(""));
+    }
+
+    #[test]
+    fn lone_ampersand_is_text() {
+        assert_eq!(tab("echo a & b"), Completion::None);
+        assert_eq!(tab("echo a &b"), Completion::None);
+        assert_eq!(tab("cat & con"), line("cat & contact.txt "));
+    }
+}
@@ -0,0 +1,397 @@
+//! Commands that read text, from files or a pipe, and print some of it back.
+
+use std::ops::Range;
+
+use super::commands::read_input;
+use super::fs;
+use super::output::{Block, Color, Span};
+use super::parser::{Args, CommandError};
+use super::registry::{CommandContext, Output, ShellCommand};
+
+/// Prints `lines`, or nothing at all when there are none.
+fn print_lines<'a>(lines: impl IntoIterator<Item = &'a str>) -> Output {
+    let text = lines.into_iter().collect::<Vec<_>>().join("\n");
+    if text.is_empty() {
+        Output::Print(Vec::new())
+    } else {
+        Output::text(text)
+    }
+}
+
+pub struct Grep;
+
+impl ShellCommand for Grep {
+    fn name(&self) -> &'static str {
+        "grep"
+    }
+
+    fn description(&self) -> &'static str {
+        "Print lines containing a pattern"
+    }
+
+    fn usage(&self) -> &'static str {
+        "grep [-i] [-v] [-n] [-c] <pattern> [file...]"
+    }
+
+    fn flags(&self) -> &'static [(char, &'static str)] {
+        &[
+            ('i', "ignore-case"),
+            ('v', "invert-match"),
+            ('n', "line-number"),
+            ('c', "count"),
+        ]
+    }
+
+    fn execute(&self, args: &Args, ctx: &CommandContext) -> Result<Output, CommandError> {
+        args.allow_flags(self.flags())?;
+        let Some((pattern, paths)) = args.positional().split_first() else {
+            return Err(CommandError::Usage);
+        };
+        let ignore_case = args.has_flag('i', "ignore-case");
+        let invert = args.has_flag('v', "invert-match");
+        let text = read_input(paths, ctx)?;
+
+        let found: Vec<(usize, &str, Vec<Range<usize>>)> = text
+            .lines()
+            .enumerate()
+            .map(|(i, line)| (i + 1, line, find_all(line, pattern, ignore_case)))
+            .filter(|(_, _, matches)| matches.is_empty() == invert)
+            .collect();
+
+        if args.has_flag('c', "count") {
+            return Ok(Output::text(found.len().to_string()));
+        }
+        let lines = found
+            .into_iter()
+            .map(|(number, line, matches)| {
+                let mut spans = Vec::new();
+                if args.has_flag('n', "line-number") {
+                    spans.push(Span::colored(format!("{number}:"), Color::Green));
+                }
+                let mut end = 0;
+                for range in matches {
+                    spans.push(Span::plain(&line[end..range.start]));
+                    spans.push(Span::colored(&line[range.clone()], Color::Maroon).bold());
+                    end = range.end;
+                }
+                spans.push(Span::plain(&line[end..]));
+                Block::Line(spans)
+            })
+            .collect();
+        Ok(Output::Print(lines))
+    }
+
+    fn complete(&self, args: &[String], partial: &str, ctx: &CommandContext) -> Vec<String> {
+        // The first argument is the pattern.
+        if args.iter().all(|arg| arg.starts_with('-')) {
+            return Vec::new();
+        }
+        fs::complete_path(ctx, partial, false)
+    }
+}
+
+/// Byte ranges of the non-overlapping occurrences of `pattern` in `line`.
+fn find_all(line: &str, pattern: &str, ignore_case: bool) -> Vec<Range<usize>> {
+    let same = |a: char, b: char| {
+        if ignore_case {
+            a.to_lowercase().eq(b.to_lowercase())
+        } else {
+            a == b
+        }
+    };
+
+    let mut matches = Vec::new();
+    if pattern.is_empty() {
+        // An empty pattern matches every line without highlighting anything.
+        matches.push(0..0);
+        return matches;
+    }
+    let mut start = 0;
+    while start < line.len() {
+        let mut end = start;
+        let mut rest = line[start..].chars();
+        let matched = pattern.chars().all(|p| match rest.next() {
+            Some(c) if same(c, p) => {
+                end += c.len_utf8();
+                true
+            }
+            _ => false,
+        });
+        if matched {
+            matches.push(start..end);
+            start = end;
+        } else {
+            start += line[start..].chars().next().map_or(1, char::len_utf8);
+        }
+    }
+    matches
+}
+
+pub struct Head;
+
+impl ShellCommand for Head {
+    fn name(&self) -> &'static str {
+        "head"
+    }
+
+    fn description(&self) -> &'static str {
+        "Print the first lines of the input"
+    }
+
+    fn usage(&self) -> &'static str {
+        "head [-n lines] [file...]"
+    }
+
+    fn options(&self) -> &'static [(char, &'static str)] {
+        &[('n', "lines")]
+    }
+
+    fn execute(&self, args: &Args, ctx: &CommandContext) -> Result<Output, CommandError> {
+        args.allow_flags(&[])?;
+        let count = match args.value("lines") {
+            Some(value) => value
+                .parse()
+                .map_err(|_| CommandError::Failed(format!("invalid number of lines: '{value}'")))?,
+            None => 10,
+        };
+        let text = read_input(args.positional(), ctx)?;
+        Ok(print_lines(text.lines().take(count)))
+    }
+
+    fn complete(&self, _args: &[String], partial: &str, ctx: &CommandContext) -> Vec<String> {
+        fs::complete_path(ctx, partial, false)
+    }
+}
+
+pub struct Wc;
+
+impl ShellCommand for Wc {
+    fn name(&self) -> &'static str {
+        "wc"
+    }
+
+    fn description(&self) -> &'static str {
+        "Count lines, words and bytes"
+    }
+
+    fn usage(&self) -> &'static str {
+        "wc [-l] [-w] [-c] [file...]"
+    }
+
+    fn flags(&self) -> &'static [(char, &'static str)] {
+        &[('l', "lines"), ('w', "words"), ('c', "bytes")]
+    }
+
+    fn execute(&self, args: &Args, ctx: &CommandContext) -> Result<Output, CommandError> {
+        args.allow_flags(self.flags())?;
+        let mut shown = [
+            args.has_flag('l', "lines"),
+            args.has_flag('w', "words"),
+            args.has_flag('c', "bytes"),
+        ];
+        if !shown.contains(&true) {
+            shown = [true; 3];
+        }
+        let row = |counts: [usize; 3], name: &str| {
+            let mut row: String = counts
+                .iter()
+                .zip(shown)
+                .filter(|(_, shown)| *shown)
+                .map(|(count, _)| format!("{count:>7}"))
+                .collect();
+            if !name.is_empty() {
+                row.push(' ');
+                row.push_str(name);
+            }
+            row
+        };
+
+        let paths = args.positional();
+        if paths.is_empty() {
+            return Ok(Output::text(row(count(&read_input(paths, ctx)?), "")));
+        }
+        let mut rows = Vec::new();
+        let mut total = [0; 3];
+        for path in paths {
+            let counts = count(&read_input(std::slice::from_ref(path), ctx)?);
+            for (total, count) in total.iter_mut().zip(counts) {
+                *total += count;
+            }
+            rows.push(row(counts, path));
+        }
+        if paths.len() > 1 {
+            rows.push(row(total, "total"));
+        }
+        Ok(Output::text(rows.join("\n")))
+    }
+
+    fn complete(&self, _args: &[String], partial: &str, ctx: &CommandContext) -> Vec<String> {
+        fs::complete_path(ctx, partial, false)
+    }
+}
+
+/// Lines, words and bytes in `text`, counting a last line without a newline.
+fn count(text: &str) -> [usize; 3] {
+    [
+        text.lines().count(),
+        text.split_whitespace().count(),
+        text.len(),
+    ]
+}
+
+pub struct Sort;
+
+impl ShellCommand for Sort {
+    fn name(&self) -> &'static str {
+        "sort"
+    }
+
+    fn description(&self) -> &'static str {
+        "Sort lines of the input"
+    }
+
+    fn usage(&self) -> &'static str {
+        "sort [-r] [-n] [-u] [file...]"
+    }
+
+    fn flags(&self) -> &'static [(char, &'static str)] {
+        &[('r', "reverse"), ('n', "numeric-sort"), ('u', "unique")]
+    }
+
+    fn execute(&self, args: &Args, ctx: &CommandContext) -> Result<Output, CommandError> {
+        args.allow_flags(self.flags())?;
+        let text = read_input(args.positional(), ctx)?;
+        let mut lines: Vec<&str> = text.lines().collect();
+
+        if args.has_flag('n', "numeric-sort") {
+            // Like coreutils, lines without a leading number sort as zero.
+            lines.sort_by(|a, b| {
+                leading_number(a)
+                    .total_cmp(&leading_number(b))
+                    .then(a.cmp(b))
+            });
+        } else {
+            lines.sort();
+        }
+        if args.has_flag('u', "unique") {
+            lines.dedup();
+        }
+        if args.has_flag('r', "reverse") {
+            lines.reverse();
+        }
+        Ok(print_lines(lines))
+    }
+
+    fn complete(&self, _args: &[String], partial: &str, ctx: &CommandContext) -> Vec<String> {
+        fs::complete_path(ctx, partial, false)
+    }
+}
+
+fn leading_number(line: &str) -> f64 {
+    let line = line.trim_start();
+    let end = line
+        .char_indices()
+        .find(|&(i, c)| !(c.is_ascii_digit() || c == '.' || (i == 0 && c == '-')))
+        .map_or(line.len(), |(i, _)| i);
+    line[..end].parse().unwrap_or(0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::terminal::commands::COMMANDS;
+    use crate::components::terminal::registry::Environment;
+
+    fn run(input: &str) -> String {
+        let env = Environment {
+            visibility: serde_json::from_str(r#"{"today":"2024-06-01","preview":false}"#).unwrap(),
+            origin: "https://example.com".to_string(),
+        };
+        COMMANDS
+            .run(input, "/", &env)
+            .iter()
+            .map(Output::plain_text)
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    const GITHUB: &str = "github    https://github.com/safstromo";
+    const LINKEDIN: &str = "linkedin  https://www.linkedin.com/in/safstromo";
+    const EMAIL: &str = "email     safstrom.oliver@gmail.com";
+
+    #[test]
+    fn grep() {
+        assert_eq!(run("grep linked contact.txt"), LINKEDIN);
+        assert_eq!(run("grep -n linked contact.txt"), format!("2:{LINKEDIN}"));
+        assert_eq!(run("grep -ic GITHUB contact.txt"), "1");
+        assert_eq!(run("grep -c GITHUB contact.txt"), "0");
+        assert_eq!(run("grep -v https contact.txt"), EMAIL);
+        assert_eq!(run("cat contact.txt | grep email"), EMAIL);
+        assert_eq!(
+            run("grep"),
+            "usage: grep [-i] [-v] [-n] [-c] <pattern> [file...]"
+        );
+    }
+
+    #[test]
+    fn find_all_matches() {
+        assert_eq!(find_all("abab", "ab", false), [0..2, 2..4]);
+        assert_eq!(find_all("aaa", "aa", false), vec![0..2]);
+        assert_eq!(find_all("ÅÄå", "å", true), [0..2, 4..6]);
+        assert_eq!(find_all("abc", "", false), vec![0..0]);
+        assert!(find_all("abc", "x", false).is_empty());
+    }
+
+    #[test]
+    fn head() {
+        assert_eq!(run("head -n1 contact.txt"), GITHUB);
+        assert_eq!(run("head -n 1 contact.txt"), GITHUB);
+        assert_eq!(
+            run("head --lines=2 contact.txt"),
+            format!("{GITHUB}\n{LINKEDIN}")
+        );
+        assert_eq!(run("head -n5 contact.txt"), run("cat contact.txt"));
+        assert_eq!(
+            run("head -n x contact.txt"),
+            "head: invalid number of lines: 'x'"
+        );
+        assert_eq!(run("head -n0 contact.txt"), "");
+    }
+
+    #[test]
+    fn wc() {
+        assert_eq!(run("wc contact.txt"), "      3      6    122 contact.txt");
+        assert_eq!(run("wc -l contact.txt"), "      3 contact.txt");
+        assert_eq!(run("cat contact.txt | wc -lw"), "      3      6");
+        assert_eq!(
+            run("wc -l contact.txt about.txt"),
+            "      3 contact.txt\n      2 about.txt\n      5 total"
+        );
+        assert_eq!(count("a b\nc"), [2, 3, 5]);
+        assert_eq!(count(""), [0, 0, 0]);
+    }
+
+    #[test]
+    fn sort() {
+        assert_eq!(
+            run("sort contact.txt"),
+            format!("{EMAIL}\n{GITHUB}\n{LINKEDIN}")
+        );
+        assert_eq!(run("sort -r contact.txt | head -n1"), LINKEDIN);
+        assert_eq!(
+            run("grep -n s contact.txt | sort -rn | head -n1"),
+            format!("3:{EMAIL}")
+        );
+        assert_eq!(
+            run("cat contact.txt contact.txt | sort -u | wc -l"),
+            "      3"
+        );
+    }
+
+    #[test]
+    fn leading_numbers() {
+        assert_eq!(leading_number("  10 apples"), 10.0);
+        assert_eq!(leading_number("-2.5x"), -2.5);
+        assert_eq!(leading_number("apples"), 0.0);
+    }
+}
@@ -0,0 +1,239 @@
+use std::collections::BTreeMap;
+
+use super::registry::{CommandContext, Environment};
+use crate::blog::{Visibility, POST_INDEX};
+
+#[derive(Debug, Clone)]
+pub enum Node {
+    File(String),
+    Dir(BTreeMap<String, Node>),
+}
+
+impl Node {
+    fn dir(entries: impl IntoIterator<Item = (String, Node)>) -> Self {
+        Node::Dir(entries.into_iter().collect())
+    }
+
+    fn file(name: &str, contents: String) -> (String, Node) {
+        (name.to_string(), Node::File(contents))
+    }
+}
+
+/// The read-only tree the terminal's `ls`, `cd`, `cat` and `tree` browse.
+/// Built for every command, since `/blog` depends on the visitor and the date.
+pub fn root(env: &Environment) -> Node {
+    Node::dir([
+        Node::file(
+            "about.txt",
+            "Oliver Säfström, fullstack developer.\n\
+             This site is written in Rust with Leptos. Type 'help' to see what else you can do."
+                .to_string(),
+        ),
+        Node::file(
+            "contact.txt",
+            "github    https://github.com/safstromo\n\
+             linkedin  https://www.linkedin.com/in/safstromo\n\
+             email     safstrom.oliver@gmail.com"
+                .to_string(),
+        ),
+        ("blog".to_string(), blog_dir(&env.visibility, &env.origin)),
+        (
+            "projects".to_string(),
+            Node::dir([
+                Node::file(
+                    "rabbitnook.txt",
+                    "This site.\nhttps://github.com/safstromo/rabbitnook".to_string(),
+                ),
+                Node::file(
+                    "esp32-aws-iot-relay.txt",
+                    "Remote relay on an ESP32 controlled over AWS IoT, written in Rust.\n\
+                     https://github.com/safstromo/esp32-aws-iot-relay"
+                        .to_string(),
+                ),
+            ]),
+        ),
+    ])
+}
+
+/// `/blog`, a file for each post `visibility` allows linking to it at `origin`.
+pub fn blog_dir(visibility: &Visibility, origin: &str) -> Node {
+    Node::dir(POST_INDEX.visible(visibility).map(|post| {
+        Node::file(
+            &format!("{}.md", post.slug),
+            format!(
+                "# {}\n{}\n\n{}\n\n{origin}{}",
+                post.title,
+                post.date,
+                post.summary,
+                post.path()
+            ),
+        )
+    }))
+}
+
+/// Turns `path` into an absolute, normalised path relative to `cwd`.
+/// `~` is the root, since visitors have no other home.
+pub fn resolve(cwd: &str, path: &str) -> String {
+    let joined = match path {
+        "~" => String::new(),
+        _ if path.starts_with('/') => path.to_string(),
+        _ if path.starts_with("~/") => path[1..].to_string(),
+        _ => format!("{cwd}/{path}"),
+    };
+
+    let mut parts: Vec<&str> = Vec::new();
+    for part in joined.split('/') {
+        match part {
+            "" | "." => {}
+            ".." => {
+                parts.pop();
+            }
+            part => parts.push(part),
+        }
+    }
+    format!("/{}", parts.join("/"))
+}
+
+/// The node at the absolute `path` under `root`.
+pub fn lookup<'a>(root: &'a Node, path: &str) -> Option<&'a Node> {
+    path.split('/')
+        .filter(|part| !part.is_empty())
+        .try_fold(root, |node, part| match node {
+            Node::Dir(entries) => entries.get(part),
+            Node::File(_) => None,
+        })
+}
+
+/// Candidates for a partially typed path, keeping whatever directory prefix
+/// was typed so they can replace the word as-is.
+pub fn complete_path(ctx: &CommandContext, partial: &str, dirs_only: bool) -> Vec<String> {
+    let (typed, dir) = match partial.rfind('/') {
+        Some(i) => (&partial[..=i], resolve(ctx.cwd, &partial[..=i])),
+        None => ("", ctx.cwd.to_string()),
+    };
+
+    let root = root(ctx.env);
+    let Some(Node::Dir(entries)) = lookup(&root, &dir) else {
+        return Vec::new();
+    };
+    entries
+        .iter()
+        .filter_map(|(name, node)| match node {
+            Node::Dir(_) => Some(format!("{typed}{name}/")),
+            Node::File(_) if !dirs_only => Some(format!("{typed}{name}")),
+            Node::File(_) => None,
+        })
+        .collect()
+}
+
+/// Renders `node` and its children with box-drawing branches like `tree`.
+pub fn tree(name: &str, node: &Node) -> String {
+    let mut lines = vec![name.to_string()];
+    let (mut dirs, mut files) = (0, 0);
+    walk(node, "", &mut lines, &mut dirs, &mut files);
+    lines.push(String::new());
+    lines.push(format!("{dirs} directories, {files} files"));
+    lines.join("\n")
+}
+
+fn walk(node: &Node, indent: &str, lines: &mut Vec<String>, dirs: &mut usize, files: &mut usize) {
+    let Node::Dir(entries) = node else {
+        return;
+    };
+    for (i, (name, child)) in entries.iter().enumerate() {
+        let last = i + 1 == entries.len();
+        let branch = if last { "└── " } else { "├── " };
+        lines.push(format!("{indent}{branch}{name}"));
+        match child {
+            Node::Dir(_) => {
+                *dirs += 1;
+                let indent = format!("{indent}{}", if last { "    " } else { "│   " });
+                walk(child, &indent, lines, dirs, files);
+            }
+            Node::File(_) => *files += 1,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tree_fixture() -> Node {
+        Node::dir([
+            Node::file("a.txt", "a".to_string()),
+            (
+                "docs".to_string(),
+                Node::dir([
+                    Node::file("b.txt", "b".to_string()),
+                    ("empty".to_string(), Node::dir([])),
+                ]),
+            ),
+            Node::file("z.txt", "z".to_string()),
+        ])
+    }
+
+    #[test]
+    fn resolves_paths() {
+        for (cwd, path, resolved) in [
+            ("/", "docs", "/docs"),
+            ("/docs", "b.txt", "/docs/b.txt"),
+            ("/docs", "/a.txt", "/a.txt"),
+            ("/docs", ".", "/docs"),
+            ("/docs", "./empty/", "/docs/empty"),
+            ("/docs", "..", "/"),
+            ("/docs", "../..", "/"),
+            ("/", "../../docs", "/docs"),
+            ("/docs/empty", "../../a.txt", "/a.txt"),
+            ("/docs", "~", "/"),
+            ("/docs", "~/", "/"),
+            ("/docs", "~/a.txt", "/a.txt"),
+            ("/", "docs//empty///", "/docs/empty"),
+            ("/docs/", "", "/docs"),
+            ("/", "~name", "/~name"),
+        ] {
+            assert_eq!(resolve(cwd, path), resolved, "{cwd} {path}");
+        }
+    }
+
+    #[test]
+    fn looks_up_nodes() {
+        let root = tree_fixture();
+        for (path, found) in [
+            ("/", Some("dir")),
+            ("", Some("dir")),
+            ("/docs", Some("dir")),
+            ("/docs/", Some("dir")),
+            ("/docs/b.txt", Some("b")),
+            ("/a.txt", Some("a")),
+            ("/missing", None),
+            ("/docs/missing.txt", None),
+            ("/a.txt/more", None),
+        ] {
+            let node = lookup(&root, path).map(|node| match node {
+                Node::File(contents) => contents.as_str(),
+                Node::Dir(_) => "dir",
+            });
+            assert_eq!(node, found, "{path}");
+        }
+    }
+
+    #[test]
+    fn draws_a_tree() {
+        assert_eq!(
+            tree(".", &tree_fixture()),
+            ".\n\
+             ├── a.txt\n\
+             ├── docs\n\
+             │   ├── b.txt\n\
+             │   └── empty\n\
+             └── z.txt\n\
+             \n\
+             2 directories, 3 files"
+        );
+        assert_eq!(
+            tree("a.txt", &Node::File(String::new())),
+            "a.txt\n\n0 directories, 0 files"
+        );
+    }
+}
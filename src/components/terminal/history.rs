@@ -0,0 +1,196 @@
+/// Key the input history is persisted under in localStorage.
+const STORAGE_KEY: &str = "rabbitnook:history";
+const MAX_ENTRIES: usize = 100;
+
+/// Lines the visitor has typed, kept separately from the rendered scrollback
+/// so that `clear` does not forget them.
+#[derive(Debug, Clone, Default)]
+pub struct InputHistory {
+    entries: Vec<String>,
+    /// Entry currently recalled with Up/Down, `None` when editing a new line.
+    cursor: Option<usize>,
+    /// The line that was being typed before navigation started.
+    draft: String,
+}
+
+impl InputHistory {
+    /// Reads the history saved by a previous visit, if any.
+    pub fn load() -> Self {
+        let entries = local_storage()
+            .and_then(|storage| storage.get_item(STORAGE_KEY).ok().flatten())
+            .map(|saved| saved.lines().map(str::to_string).collect())
+            .unwrap_or_default();
+
+        InputHistory {
+            entries,
+            ..Default::default()
+        }
+    }
+
+    pub fn save(&self) {
+        if let Some(storage) = local_storage() {
+            let _ = storage.set_item(STORAGE_KEY, &self.entries.join("\n"));
+        }
+    }
+
+    /// Records a submitted line, skipping blanks and immediate repeats like bash's
+    /// `ignoredups`.
+    pub fn push(&mut self, line: &str) {
+        self.cursor = None;
+        self.draft.clear();
+
+        let line = line.trim();
+        if line.is_empty() || self.entries.last().is_some_and(|last| last == line) {
+            return;
+        }
+        self.entries.push(line.to_string());
+        if self.entries.len() > MAX_ENTRIES {
+            self.entries.remove(0);
+        }
+    }
+
+    /// Steps one entry back in time, remembering `current` so Down can restore it.
+    pub fn previous(&mut self, current: &str) -> Option<&str> {
+        let index = match self.cursor {
+            _ if self.entries.is_empty() => return None,
+            None => {
+                self.draft = current.to_string();
+                self.entries.len() - 1
+            }
+            Some(index) => index.saturating_sub(1),
+        };
+        self.cursor = Some(index);
+        Some(&self.entries[index])
+    }
+
+    /// Steps one entry forward, ending on the line that was being typed.
+    pub fn next(&mut self) -> Option<String> {
+        let index = self.cursor?;
+        if index + 1 < self.entries.len() {
+            self.cursor = Some(index + 1);
+            Some(self.entries[index + 1].clone())
+        } else {
+            self.cursor = None;
+            Some(std::mem::take(&mut self.draft))
+        }
+    }
+
+    /// Finds the newest entry containing `query` that is older than `before`.
+    pub fn search(&self, query: &str, before: usize) -> Option<(usize, &str)> {
+        self.entries[..before.min(self.entries.len())]
+            .iter()
+            .enumerate()
+            .rev()
+            .find(|(_, entry)| entry.contains(query))
+            .map(|(index, entry)| (index, entry.as_str()))
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+}
+
+fn local_storage() -> Option<web_sys::Storage> {
+    web_sys::window()?.local_storage().ok().flatten()
+}
+
+/// State of an in-progress Ctrl+R search.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReverseSearch {
+    pub query: String,
+    /// Index and text of the current match.
+    pub found: Option<(usize, String)>,
+}
+
+impl ReverseSearch {
+    /// Re-runs the search from the newest entry after the query changed.
+    pub fn update(&mut self, query: String, history: &InputHistory) {
+        self.found = history
+            .search(&query, history.len())
+            .map(|(index, entry)| (index, entry.to_string()));
+        self.query = query;
+    }
+
+    /// Moves to the next older match, keeping the current one if there is none.
+    pub fn older(&mut self, history: &InputHistory) {
+        let before = self
+            .found
+            .as_ref()
+            .map_or(history.len(), |(index, _)| *index);
+        if let Some((index, entry)) = history.search(&self.query, before) {
+            self.found = Some((index, entry.to_string()));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn history(lines: &[&str]) -> InputHistory {
+        let mut history = InputHistory::default();
+        for line in lines {
+            history.push(line);
+        }
+        history
+    }
+
+    #[test]
+    fn ignores_blanks_and_repeats() {
+        let history = history(&["ls", "ls", " ls ", "", "  ", "pwd", "ls"]);
+        assert_eq!(history.entries, ["ls", "pwd", "ls"]);
+    }
+
+    #[test]
+    fn keeps_the_newest_entries() {
+        let lines: Vec<String> = (0..MAX_ENTRIES + 5).map(|i| format!("echo {i}")).collect();
+        let history = history(&lines.iter().map(String::as_str).collect::<Vec<_>>());
+        assert_eq!(history.len(), MAX_ENTRIES);
+        assert_eq!(history.entries[0], "echo 5");
+        assert_eq!(
+            history.entries[MAX_ENTRIES - 1],
+            format!("echo {}", MAX_ENTRIES + 4)
+        );
+    }
+
+    #[test]
+    fn up_then_down_restores_the_draft() {
+        let mut history = history(&["one", "two"]);
+        assert_eq!(history.next(), None);
+        assert_eq!(history.previous("dra"), Some("two"));
+        assert_eq!(history.previous("two"), Some("one"));
+        assert_eq!(history.previous("one"), Some("one"));
+        assert_eq!(history.next().as_deref(), Some("two"));
+        assert_eq!(history.next().as_deref(), Some("dra"));
+        assert_eq!(history.next(), None);
+
+        // Submitting forgets the draft and starts from the newest entry again.
+        history.previous("old draft");
+        history.push("three");
+        assert_eq!(history.previous(""), Some("three"));
+        assert_eq!(history.next().as_deref(), Some(""));
+
+        assert_eq!(InputHistory::default().previous("x"), None);
+    }
+
+    #[test]
+    fn search_steps_to_older_matches() {
+        let history = history(&["cat a.txt", "ls", "cat b.txt", "pwd", "cat c.txt"]);
+        assert_eq!(history.search("cat", history.len()), Some((4, "cat c.txt")));
+        assert_eq!(history.search("cat", 4), Some((2, "cat b.txt")));
+        assert_eq!(history.search("cat", 2), Some((0, "cat a.txt")));
+        assert_eq!(history.search("cat", 0), None);
+        assert_eq!(history.search("nope", history.len()), None);
+        assert_eq!(history.search("ls", 99), Some((1, "ls")));
+
+        let mut search = ReverseSearch::default();
+        search.update("cat".to_string(), &history);
+        search.older(&history);
+        assert_eq!(search.found, Some((2, "cat b.txt".to_string())));
+        search.older(&history);
+        search.older(&history);
+        assert_eq!(search.found, Some((0, "cat a.txt".to_string())));
+        search.update("pw".to_string(), &history);
+        assert_eq!(search.found, Some((3, "pwd".to_string())));
+    }
+}
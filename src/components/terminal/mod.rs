@@ -0,0 +1,463 @@
+mod commands;
+mod completion;
+mod filters;
+mod fs;
+mod history;
+mod output;
+mod parser;
+mod registry;
+mod session;
+
+use leptos::*;
+
+use leptos::prelude::*;
+use leptos::server_fn::ServerFn;
+use leptos::task::spawn_local;
+use leptos_router::hooks::use_navigate;
+use leptos_router::NavigateOptions;
+
+use crate::blog::{use_visibility, Visibility};
+use crate::site::use_site_origin;
+use commands::COMMANDS;
+use completion::{complete, Completion};
+use history::{InputHistory, ReverseSearch};
+use output::{Block, Span};
+use registry::{Environment, Output};
+use session::{clock, now, run_on_server, Action, Command, RunTerminal};
+pub use session::{load_session, Session, DEFAULT_SCROLLBACK};
+
+#[component]
+pub fn TerminalInput(
+    session: RwSignal<Session>,
+    input_element: NodeRef<html::Input>,
+    /// How many entries the scrollback holds.
+    #[prop(default = DEFAULT_SCROLLBACK)]
+    scrollback: usize,
+) -> impl IntoView {
+    let (input, _set_input) = signal("".to_string());
+    // let input_element: NodeRef<html::Input> = create_node_ref();
+    let cwd = Signal::derive(move || session.with(|session| session.cwd.clone()));
+    let navigate = use_navigate();
+    let history = StoredValue::new(InputHistory::default());
+    let (search, set_search) = signal(None::<ReverseSearch>);
+    let (visibility, origin) = (use_visibility(), use_site_origin());
+    // What everyone sees, with relative links, until both have loaded.
+    let env = move || Environment {
+        visibility: visibility
+            .get_untracked()
+            .unwrap_or_else(Visibility::public),
+        origin: origin.get_untracked().unwrap_or_default(),
+    };
+
+    // Effects only run in the browser, which is where the saved history lives.
+    Effect::new(move |_| history.set_value(InputHistory::load()));
+
+    let set_input_value = move |value: &str| {
+        input_element
+            .get()
+            .expect("input element should be mounted")
+            .set_value(value);
+    };
+
+    let on_keydown = move |ev: leptos::ev::KeyboardEvent| {
+        let searching = search.get_untracked();
+        match ev.key().as_str() {
+            "r" if ev.ctrl_key() => {
+                ev.prevent_default();
+                match searching {
+                    Some(mut searching) => {
+                        history.with_value(|history| searching.older(history));
+                        set_search.set(Some(searching));
+                    }
+                    None => {
+                        set_input_value("");
+                        set_search.set(Some(ReverseSearch::default()));
+                    }
+                }
+            }
+            "Escape" if searching.is_some() => set_search.set(None),
+            "g" if ev.ctrl_key() && searching.is_some() => {
+                ev.prevent_default();
+                set_search.set(None);
+            }
+            "ArrowUp" | "ArrowDown" | "ArrowLeft" | "ArrowRight" | "Tab" if searching.is_some() => {
+                // Like readline, moving the cursor accepts the match for editing.
+                ev.prevent_default();
+                if let Some((_, found)) = searching.and_then(|searching| searching.found) {
+                    set_input_value(&found);
+                }
+                set_search.set(None);
+            }
+            "ArrowUp" => {
+                ev.prevent_default();
+                let current = input_element
+                    .get()
+                    .expect("input element should be mounted")
+                    .value();
+                if let Some(previous) = history
+                    .try_update_value(|history| history.previous(&current).map(str::to_string))
+                    .flatten()
+                {
+                    set_input_value(&previous);
+                }
+            }
+            "ArrowDown" => {
+                ev.prevent_default();
+                if let Some(next) = history.try_update_value(|history| history.next()).flatten() {
+                    set_input_value(&next);
+                }
+            }
+            "Tab" => {
+                ev.prevent_default();
+                let current = input_element
+                    .get()
+                    .expect("input element should be mounted")
+                    .value();
+                match complete(&COMMANDS, &current, &cwd.get_untracked(), &env()) {
+                    Completion::None => {}
+                    Completion::Line(line) => set_input_value(&line),
+                    Completion::Candidates(candidates) => {
+                        session.update(|session| {
+                            session.print(
+                                &current,
+                                vec![Block::Text(candidates.join("  "))],
+                                now(),
+                            );
+                            session.truncate(scrollback);
+                        });
+                    }
+                }
+            }
+            _ => {}
+        }
+    };
+
+    let on_input = move |ev: leptos::ev::Event| {
+        if search.get_untracked().is_some() {
+            let query = event_target_value(&ev);
+            set_search.update(|searching| {
+                if let Some(searching) = searching {
+                    history.with_value(|history| searching.update(query, history));
+                }
+            });
+        }
+    };
+
+    let on_submit = move |ev: leptos::ev::SubmitEvent| {
+        // stop the page from reloading!
+        ev.prevent_default();
+
+        // here, we'll extract the value from the input
+        let value = input_element
+            .get()
+            // event handlers can only fire after the view
+            // is mounted to the DOM, so the `NodeRef` will be `Some`
+            .expect("<input> should be mounted")
+            // `leptos::HtmlElement<html::Input>` implements `Deref`
+            // to a `web_sys::HtmlInputElement`.
+            // this means we can call`HtmlInputElement::value()`
+            // to get the current value of the input
+            .value();
+        // Enter during a reverse search runs the match, or the query if nothing matched.
+        let value = match search.get_untracked() {
+            Some(ReverseSearch {
+                found: Some((_, found)),
+                ..
+            }) => found,
+            _ => value,
+        };
+        set_search.set(None);
+        history.update_value(|history| {
+            history.push(&value);
+            history.save();
+        });
+
+        if COMMANDS.needs_server(&value) {
+            // Recorded once the server has answered.
+            let (cwd, timestamp, navigate) = (cwd.get_untracked(), now(), navigate.clone());
+            spawn_local(async move {
+                let outputs = run_on_server(value.clone(), cwd.clone())
+                    .await
+                    .unwrap_or_else(|err| vec![Output::text(format!("rabbitnook: {err}"))]);
+                let actions = session
+                    .try_update(|session| {
+                        let actions = session.record(&value, cwd, timestamp, outputs);
+                        session.truncate(scrollback);
+                        actions
+                    })
+                    .unwrap_or_default();
+                perform(actions, &navigate);
+            });
+        } else {
+            let actions = session
+                .try_update(|session| {
+                    let actions = session.run(&value, now(), &env());
+                    session.truncate(scrollback);
+                    actions
+                })
+                .unwrap_or_default();
+            perform(actions, &navigate);
+        }
+        set_input_value("");
+    };
+
+    view! {
+        <TerminalPwd cwd=cwd />
+        <section class="flex flex-row w-full">
+            <svg
+                class="w-6 h-6 text-green"
+                aria-hidden="true"
+                xmlns="http://www.w3.org/2000/svg"
+                width="24"
+                height="24"
+                fill="none"
+                viewBox="0 0 24 24"
+            >
+                <path
+                    stroke="currentColor"
+                    stroke-linecap="round"
+                    stroke-linejoin="round"
+                    stroke-width="2"
+                    d="m9 5 7 7-7 7"
+                ></path>
+            </svg>
+            // TODO: Fix input
+            // Without WASM the form posts to the server, which runs the line instead.
+            <form
+                class="w-full"
+                method="post"
+                action=RunTerminal::url()
+                on:submit=on_submit
+            >
+                <input
+                    class="w-5/6 mx-2 bg-base border-none text-white focus:outline-none"
+                    type="text"
+                    name="input"
+                    value=input
+                    node_ref=input_element
+                    on:keydown=on_keydown
+                    on:input=on_input
+                    id="terminal-input"
+                    autofocus
+                />
+            // TODO: Add caret
+            // <span class="caret"></span>
+            </form>
+        </section>
+        {move || {
+            search
+                .get()
+                .map(|searching| {
+                    let found = searching.found.map(|(_, found)| found).unwrap_or_default();
+                    view! {
+                        <p class="text-white mx-2">
+                            "(reverse-i-search)`"{searching.query}"': "{found}
+                        </p>
+                    }
+                })
+        }}
+    }
+}
+
+/// Does what the commands of a line left to the page, in order.
+fn perform(actions: Vec<Action>, navigate: &impl Fn(&str, NavigateOptions)) {
+    for action in actions {
+        match action {
+            Action::OpenLink(url) => open_link(url),
+            Action::Navigate(path) => navigate(&path, Default::default()),
+        }
+    }
+}
+
+fn open_link(url: String) {
+    Effect::new(move |_| {
+        let window = web_sys::window().expect("window should be available");
+        window.open_with_url_and_target(&url, "_blank").unwrap();
+    });
+}
+
+#[component]
+pub fn TerminalHistory(session: RwSignal<Session>) -> impl IntoView {
+    let list = NodeRef::<html::Ul>::new();
+
+    // Keep the newest output in view, like a real terminal.
+    Effect::new(move |_| {
+        session.track();
+        if let Some(list) = list.get() {
+            list.set_scroll_top(list.scroll_height());
+        }
+    });
+
+    view! {
+        <ul class="flex-1 min-h-0 overflow-y-auto" node_ref=list>
+            <For
+                each=move || session.with(|session| session.entries.clone())
+                key=|command| command.id
+                let:child
+            >
+                <TerminalCommand command=child />
+            </For>
+
+        </ul>
+    }
+}
+
+#[component]
+fn TerminalCommand(command: Command) -> impl IntoView {
+    view! {
+        <li class="mb-2">
+            <TerminalPwd cwd=command.cwd.clone() time=clock(command.timestamp) />
+            <div class="flex flex-row">
+                <svg
+                    class="w-6 h-6 text-green"
+                    aria-hidden="true"
+                    xmlns="http://www.w3.org/2000/svg"
+                    width="24"
+                    height="24"
+                    fill="none"
+                    viewBox="0 0 24 24"
+                >
+                    <path
+                        stroke="currentColor"
+                        stroke-linecap="round"
+                        stroke-linejoin="round"
+                        stroke-width="2"
+                        d="m9 5 7 7-7 7"
+                    ></path>
+                </svg>
+                <p class="text-white mx-2">{command.command}</p>
+            </div>
+            <div class="text-white mx-2 min-w-0">
+                {command
+                    .output
+                    .into_iter()
+                    .map(|block| view! { <TerminalBlock block=block /> })
+                    .collect_view()}
+            </div>
+        </li>
+    }
+}
+
+#[component]
+fn TerminalBlock(block: Block) -> impl IntoView {
+    match block {
+        Block::Text(text) => {
+            view! { <p class="text-white whitespace-pre-wrap">{text}</p> }.into_any()
+        }
+        Block::Line(spans) => view! { <p class="text-white">{render_spans(spans)}</p> }.into_any(),
+        Block::Table(rows) => view! {
+            <table class="text-white">
+                <tbody>
+                    {rows
+                        .into_iter()
+                        .map(|row| {
+                            view! {
+                                <tr>
+                                    {row
+                                        .into_iter()
+                                        .map(|cell| {
+                                            view! {
+                                                <td class="pr-4 align-top">
+                                                    {render_spans(vec![cell])}
+                                                </td>
+                                            }
+                                        })
+                                        .collect_view()}
+                                </tr>
+                            }
+                        })
+                        .collect_view()}
+                </tbody>
+            </table>
+        }
+        .into_any(),
+        Block::Links(links) => view! {
+            <ul>
+                {links
+                    .into_iter()
+                    .map(|link| {
+                        view! {
+                            <li>
+                                <a class="text-blue" href=link.href target="_blank">
+                                    {link.label}
+                                </a>
+                            </li>
+                        }
+                    })
+                    .collect_view()}
+            </ul>
+        }
+        .into_any(),
+        Block::Pre(spans) => view! {
+            <pre class="text-white overflow-x-auto">{render_spans(spans)}</pre>
+        }
+        .into_any(),
+    }
+}
+
+fn render_spans(spans: Vec<Span>) -> impl IntoView {
+    spans
+        .into_iter()
+        .map(|span| {
+            let class = match (span.color, span.bold) {
+                (Some(color), true) => format!("{} font-semibold", color.class()),
+                (Some(color), false) => color.class().to_string(),
+                (None, true) => "font-semibold".to_string(),
+                (None, false) => String::new(),
+            };
+            view! { <span class=class>{span.text}</span> }
+        })
+        .collect_view()
+}
+
+#[component]
+fn TerminalPwd(
+    #[prop(into)] cwd: Signal<String>,
+    /// When the command ran, shown for entries in the scrollback.
+    #[prop(optional)]
+    time: Option<String>,
+) -> impl IntoView {
+    view! {
+        <section class="flex flex-row mx-2 items-start gap-1">
+            <p class="text-teal text-lg font-semibold">
+                {move || format!("rabbitnook{}", cwd.get().trim_end_matches('/'))}
+            </p>
+            <p class="text-white text-lg font-semibold">on</p>
+            <svg
+                class="w-6 h-6 text-pink"
+                aria-hidden="true"
+                xmlns="http://www.w3.org/2000/svg"
+                width="24"
+                height="24"
+                fill="currentColor"
+                viewBox="0 0 24 24"
+            >
+                <path d="M8 3a3 3 0 0 0-1 5.83v6.34a3.001 3.001 0 1 0 2 0V15a2 2 0 0 1 2-2h1a5.002 5.002 0 0 0 4.927-4.146A3.001 3.001 0 0 0 16 3a3 3 0 0 0-1.105 5.79A3.001 3.001 0 0 1 12 11h-1c-.729 0-1.412.195-2 .535V8.83A3.001 3.001 0 0 0 8 3Z"></path>
+            </svg>
+            <p class="text-pink text-lg font-semibold">main</p>
+
+            <svg
+                class="w-6 h-6 text-peach mx-1"
+                fill="currentColor"
+                width="1200pt"
+                height="1200pt"
+                version="1.1"
+                viewBox="0 0 1200 1200"
+                xmlns="http://www.w3.org/2000/svg"
+            >
+                <g>
+                    <path d="m1200 392.95c0-0.80469-0.10938-1.5977-0.16797-2.3867-0.046875-0.74219-0.046875-1.5117-0.15625-2.2578-0.10938-0.75781-0.28906-1.5-0.44531-2.2422-0.15625-0.78125-0.26562-1.5586-0.46875-2.3281-0.19141-0.69531-0.46875-1.3672-0.70703-2.0508-0.25391-0.76953-0.48047-1.5703-0.78125-2.3281-0.28906-0.68359-0.67188-1.3203-0.99609-2.0039-0.33594-0.70703-0.64844-1.4414-1.043-2.125-0.39453-0.68359-0.86328-1.3086-1.3086-1.957-0.40625-0.61328-0.77734-1.2734-1.2461-1.875-0.48047-0.63672-1.0547-1.2227-1.5859-1.8359-0.49219-0.53906-0.92578-1.1289-1.4531-1.6562-0.55078-0.53906-1.1758-1.0312-1.7539-1.5469-0.58984-0.50391-1.1289-1.043-1.7539-1.5234-1.2344-0.96094-2.543-1.8242-3.9102-2.6289l-565.73-326.61c-11.027-6.3711-24.625-6.3477-35.641 0.046875l-561.84 326.51s-0.011718 0-0.011718 0.011718c0 0-0.011719 0-0.011719 0.011719l-0.10938 0.058594c-0.60156 0.35938-1.1406 0.78125-1.7266 1.1641-0.70703 0.48047-1.4531 0.91016-2.125 1.4414-0.63672 0.48047-1.1992 1.0312-1.7891 1.5586-0.5625 0.50391-1.1641 0.97266-1.7031 1.5-0.57422 0.58984-1.0781 1.2344-1.6211 1.8477-0.46875 0.53906-0.97266 1.0664-1.4141 1.6211-0.50391 0.66016-0.92578 1.3672-1.3789 2.0625-0.38281 0.58984-0.81641 1.1523-1.1758 1.7656-0.39453 0.69531-0.70703 1.4297-1.0664 2.1602-0.32812 0.64844-0.69922 1.2852-0.97656 1.9688-0.29688 0.71875-0.51563 1.4883-0.76563 2.2305-0.25391 0.70703-0.52734 1.3906-0.73047 2.125-0.20312 0.76953-0.32422 1.5703-0.48047 2.3633-0.14453 0.73047-0.33594 1.4531-0.44531 2.1953-0.10937 0.79297-0.10937 1.6094-0.16797 2.4102-0.046875 0.74219-0.15625 1.4648-0.15625 2.2188l-1.1523 414.03c-0.035156 12.73 6.7422 24.516 17.773 30.875l565.74 326.63c5.4961 3.1914 11.641 4.7656 17.762 4.7656 1.5469 0 3.0859-0.097656 4.6211-0.28906 0.27734-0.046875 0.55078-0.13281 0.82812-0.17969 1.2461-0.19141 2.4844-0.40625 3.7188-0.74219 0.27734-0.058594 0.55078-0.20312 0.82812-0.26562 1.2109-0.35938 2.4102-0.74219 3.6016-1.2227 0.33594-0.15625 0.66016-0.33594 0.99609-0.49219 1.0547-0.48047 2.125-0.96094 3.1562-1.5469 0.023437-0.023437 0.046874-0.035156 0.070312-0.046875 0.023438-0.023438 0.046875-0.023438 0.058594-0.035156l562-326.61c10.906-6.3359 17.652-18 17.688-30.637l1.1641-413.99v-0.070313-0.085937zm-601.23-285.48 212.96 122.95-492.72 284.47-211.36-122.03zm-32.859 964.67-494.75-285.65 0.94922-332.04 494.73 285.64zm36.492-393.64-212.29-122.55 492.73-284.47 210.67 121.63zm525.38 108.01-490.77 285.21 0.9375-331.64 490.78-285.2z"></path>
+                    <path d="m525.8 1001-212.44-122.65 0.39844-142.58 212.45 122.65z"></path>
+                </g>
+            </svg>
+            <p class="text-peach text-lg font-semibold">v1.0.0</p>
+            {time
+                .map(|time| {
+                    view! {
+                        <p class="text-white text-lg font-semibold">at</p>
+                        <p class="text-sky text-lg font-semibold">{time}</p>
+                    }
+                })}
+        </section>
+    }
+}
@@ -0,0 +1,122 @@
+use serde::{Deserialize, Serialize};
+
+/// Colors from the Catppuccin palette defined in `style/tailwind.css`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Color {
+    Sky,
+    Blue,
+    Maroon,
+    Peach,
+    Green,
+    Teal,
+    Pink,
+}
+
+impl Color {
+    /// Tailwind class for the color. Spelled out in full so the class scanner
+    /// picks them up.
+    pub fn class(self) -> &'static str {
+        match self {
+            Color::Sky => "text-sky",
+            Color::Blue => "text-blue",
+            Color::Maroon => "text-maroon",
+            Color::Peach => "text-peach",
+            Color::Green => "text-green",
+            Color::Teal => "text-teal",
+            Color::Pink => "text-pink",
+        }
+    }
+}
+
+/// A run of text with an optional color.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Span {
+    pub text: String,
+    pub color: Option<Color>,
+    pub bold: bool,
+}
+
+impl Span {
+    pub fn plain(text: impl Into<String>) -> Self {
+        Span {
+            text: text.into(),
+            color: None,
+            bold: false,
+        }
+    }
+
+    pub fn colored(text: impl Into<String>, color: Color) -> Self {
+        Span {
+            color: Some(color),
+            ..Span::plain(text)
+        }
+    }
+
+    pub fn bold(self) -> Self {
+        Span { bold: true, ..self }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Link {
+    pub href: String,
+    pub label: String,
+}
+
+/// One piece of what a command prints.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Block {
+    /// Plain text, newlines are kept.
+    Text(String),
+    /// A single line of styled spans.
+    Line(Vec<Span>),
+    /// Rows of cells, aligned in columns.
+    Table(Vec<Vec<Span>>),
+    /// Links, one per line.
+    Links(Vec<Link>),
+    /// Preformatted text such as ASCII art, spacing is kept exactly.
+    Pre(Vec<Span>),
+}
+
+impl Block {
+    /// The block as unstyled text, which is what a pipe passes along.
+    pub fn plain_text(&self) -> String {
+        fn join(spans: &[Span]) -> String {
+            spans.iter().map(|span| span.text.as_str()).collect()
+        }
+
+        match self {
+            Block::Text(text) => text.clone(),
+            Block::Line(spans) | Block::Pre(spans) => join(spans),
+            Block::Table(rows) => {
+                let mut widths = Vec::new();
+                for row in rows {
+                    for (i, cell) in row.iter().enumerate() {
+                        let width = cell.text.chars().count();
+                        match widths.get_mut(i) {
+                            Some(max) if *max < width => *max = width,
+                            Some(_) => {}
+                            None => widths.push(width),
+                        }
+                    }
+                }
+                rows.iter()
+                    .map(|row| {
+                        let cells: Vec<String> = row
+                            .iter()
+                            .zip(&widths)
+                            .map(|(cell, width)| format!("{:width$}", cell.text))
+                            .collect();
+                        cells.join("  ").trim_end().to_string()
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            }
+            Block::Links(links) => links
+                .iter()
+                .map(|link| link.href.as_str())
+                .collect::<Vec<_>>()
+                .join("\n"),
+        }
+    }
+}
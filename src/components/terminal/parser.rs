@@ -0,0 +1,443 @@
+use thiserror::Error;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ParseError {
+    #[error("unexpected end of input, missing closing {0}")]
+    UnterminatedQuote(char),
+    #[error("unexpected end of input after \\")]
+    TrailingEscape,
+    #[error("syntax error near unexpected token `{0}`")]
+    UnexpectedToken(&'static str),
+}
+
+/// A shell operator between commands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operator {
+    /// `|`, feeds the output of one command to the next.
+    Pipe,
+    /// `&&`, runs the next command if the previous one succeeded.
+    And,
+    /// `||`, runs the next command if the previous one failed.
+    Or,
+    /// `;`, runs the next command regardless.
+    Then,
+}
+
+impl Operator {
+    fn symbol(self) -> &'static str {
+        match self {
+            Operator::Pipe => "|",
+            Operator::And => "&&",
+            Operator::Or => "||",
+            Operator::Then => ";",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Token {
+    Word(String),
+    Operator(Operator),
+}
+
+/// Commands joined by pipes, run together as one step of a chain.
+pub type Pipeline = Vec<Vec<String>>;
+
+/// A pipeline and how it depends on the step before it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Step {
+    /// `Then` for the first step.
+    pub condition: Operator,
+    pub pipeline: Pipeline,
+}
+
+/// Splits a line into pipelines chained with `&&`, `||` and `;`.
+///
+/// A trailing `;` is allowed, any other operator needs a command on both sides.
+pub fn parse(input: &str) -> Result<Vec<Step>, ParseError> {
+    let mut steps = Vec::new();
+    let mut condition = Operator::Then;
+    let mut pipeline: Pipeline = Vec::new();
+    let mut words = Vec::new();
+
+    for token in tokenize(input)? {
+        match token {
+            Token::Word(word) => words.push(word),
+            Token::Operator(operator) if words.is_empty() => {
+                return Err(ParseError::UnexpectedToken(operator.symbol()));
+            }
+            Token::Operator(Operator::Pipe) => pipeline.push(std::mem::take(&mut words)),
+            Token::Operator(operator) => {
+                pipeline.push(std::mem::take(&mut words));
+                steps.push(Step {
+                    condition,
+                    pipeline: std::mem::take(&mut pipeline),
+                });
+                condition = operator;
+            }
+        }
+    }
+
+    if !words.is_empty() {
+        pipeline.push(words);
+        steps.push(Step {
+            condition,
+            pipeline,
+        });
+    } else if !pipeline.is_empty() || !matches!(condition, Operator::Then) {
+        // A pipe or `&&`/`||` with nothing after it.
+        let dangling = if pipeline.is_empty() {
+            condition
+        } else {
+            Operator::Pipe
+        };
+        return Err(ParseError::UnexpectedToken(dangling.symbol()));
+    }
+
+    Ok(steps)
+}
+
+/// Splits a line into words the way a POSIX shell would.
+///
+/// Single quotes keep everything literal, double quotes allow `\"` and `\\`
+/// escapes, and a backslash outside quotes escapes the next character.
+/// Unquoted `|`, `||`, `&&` and `;` end the current word and become operators.
+pub fn tokenize(input: &str) -> Result<Vec<Token>, ParseError> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    // Tracks `""` so that an empty quoted string still counts as a word.
+    let mut in_word = false;
+    let mut chars = input.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' => {
+                in_word = true;
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(c) => current.push(c),
+                        None => return Err(ParseError::UnterminatedQuote('\'')),
+                    }
+                }
+            }
+            '"' => {
+                in_word = true;
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some(c @ ('"' | '\\')) => current.push(c),
+                            Some(c) => {
+                                current.push('\\');
+                                current.push(c);
+                            }
+                            None => return Err(ParseError::UnterminatedQuote('"')),
+                        },
+                        Some(c) => current.push(c),
+                        None => return Err(ParseError::UnterminatedQuote('"')),
+                    }
+                }
+            }
+            '\\' => {
+                in_word = true;
+                match chars.next() {
+                    Some(c) => current.push(c),
+                    None => return Err(ParseError::TrailingEscape),
+                }
+            }
+            c if c.is_whitespace() => {
+                if in_word {
+                    tokens.push(Token::Word(std::mem::take(&mut current)));
+                    in_word = false;
+                }
+            }
+            '|' | ';' | '&' => {
+                let operator = match (c, chars.peek()) {
+                    ('|', Some('|')) => Some(Operator::Or),
+                    ('|', _) => Some(Operator::Pipe),
+                    ('&', Some('&')) => Some(Operator::And),
+                    (';', _) => Some(Operator::Then),
+                    // A lone `&` would background a job, here it is just text.
+                    _ => None,
+                };
+                match operator {
+                    Some(operator) => {
+                        if matches!(operator, Operator::Or | Operator::And) {
+                            chars.next();
+                        }
+                        if in_word {
+                            tokens.push(Token::Word(std::mem::take(&mut current)));
+                            in_word = false;
+                        }
+                        tokens.push(Token::Operator(operator));
+                    }
+                    None => {
+                        in_word = true;
+                        current.push(c);
+                    }
+                }
+            }
+            c => {
+                in_word = true;
+                current.push(c);
+            }
+        }
+    }
+    if in_word {
+        tokens.push(Token::Word(current));
+    }
+
+    Ok(tokens)
+}
+
+/// Arguments passed to a command, split into flags and positionals.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Args {
+    positional: Vec<String>,
+    flags: Vec<String>,
+    /// Options that take a value, e.g. `-n 5`, keyed by their long name.
+    values: Vec<(String, String)>,
+}
+
+impl Args {
+    /// `-abc` is read as three short flags and `--name` as one long flag.
+    /// Everything after `--` is positional.
+    ///
+    /// Names in `options` take the following word as their value instead,
+    /// also written `-n5` or `--name=value`.
+    pub fn parse(tokens: &[String], options: &[(char, &str)]) -> Self {
+        let mut args = Args::default();
+        let mut tokens = tokens.iter();
+
+        while let Some(token) = tokens.next() {
+            if token == "--" {
+                args.positional.extend(tokens.cloned());
+                break;
+            } else if let Some(long) = token.strip_prefix("--") {
+                let (name, value) = match long.split_once('=') {
+                    Some((name, value)) => (name, Some(value.to_string())),
+                    None => (long, None),
+                };
+                match options.iter().find(|(_, option)| *option == name) {
+                    Some((_, option)) => {
+                        let value = value.or_else(|| tokens.next().cloned()).unwrap_or_default();
+                        args.values.push((option.to_string(), value));
+                    }
+                    None => args.flags.push(long.to_string()),
+                }
+            } else if let Some(short) = token.strip_prefix('-').filter(|short| !short.is_empty()) {
+                for (i, flag) in short.char_indices() {
+                    if let Some((_, option)) = options.iter().find(|(option, _)| *option == flag) {
+                        let rest = &short[i + flag.len_utf8()..];
+                        let value = match rest {
+                            "" => tokens.next().cloned().unwrap_or_default(),
+                            rest => rest.to_string(),
+                        };
+                        args.values.push((option.to_string(), value));
+                        break;
+                    }
+                    args.flags.push(flag.to_string());
+                }
+            } else {
+                args.positional.push(token.clone());
+            }
+        }
+
+        args
+    }
+
+    pub fn positional(&self) -> &[String] {
+        &self.positional
+    }
+
+    pub fn first(&self) -> Option<&str> {
+        self.positional.first().map(String::as_str)
+    }
+
+    pub fn has_flag(&self, short: char, long: &str) -> bool {
+        self.flags
+            .iter()
+            .any(|flag| flag == long || flag.chars().eq([short]))
+    }
+
+    /// Value of an option declared by the command, the last one if repeated.
+    pub fn value(&self, long: &str) -> Option<&str> {
+        self.values
+            .iter()
+            .rev()
+            .find(|(name, _)| name == long)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Fails with the first flag not in `allowed`.
+    pub fn allow_flags(&self, allowed: &[(char, &str)]) -> Result<(), CommandError> {
+        match self.flags.iter().find(|flag| {
+            !allowed
+                .iter()
+                .any(|(short, long)| flag == long || flag.chars().eq([*short]))
+        }) {
+            Some(flag) => Err(CommandError::UnknownFlag(flag.clone())),
+            None => Ok(()),
+        }
+    }
+
+    /// Fails unless there are at most `max` positionals and no flags.
+    pub fn at_most(&self, max: usize) -> Result<(), CommandError> {
+        self.allow_flags(&[])?;
+        if self.positional.len() > max {
+            return Err(CommandError::Usage);
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum CommandError {
+    /// The arguments did not match the command's usage string.
+    #[error("invalid usage")]
+    Usage,
+    #[error("unknown option: {}", dashed(.0))]
+    UnknownFlag(String),
+    #[error("{0}")]
+    Failed(String),
+}
+
+fn dashed(flag: &str) -> String {
+    if flag.chars().count() == 1 {
+        format!("-{flag}")
+    } else {
+        format!("--{flag}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn words(input: &str) -> Vec<String> {
+        tokenize(input)
+            .unwrap()
+            .into_iter()
+            .map(|token| match token {
+                Token::Word(word) => word,
+                Token::Operator(operator) => operator.symbol().to_string(),
+            })
+            .collect()
+    }
+
+    fn args(input: &str, options: &[(char, &str)]) -> Args {
+        Args::parse(&words(input), options)
+    }
+
+    #[test]
+    fn chains_pipelines() {
+        let steps = parse("ls | wc -l && echo ok || echo fail; pwd").unwrap();
+        let conditions: Vec<_> = steps.iter().map(|step| step.condition).collect();
+        assert_eq!(
+            conditions,
+            [Operator::Then, Operator::And, Operator::Or, Operator::Then]
+        );
+        assert_eq!(steps[0].pipeline, [vec!["ls"], vec!["wc", "-l"]]);
+        assert_eq!(steps[3].pipeline, [vec!["pwd"]]);
+    }
+
+    #[test]
+    fn allows_only_a_trailing_semicolon() {
+        assert_eq!(parse("ls;").unwrap().len(), 1);
+        assert_eq!(parse("ls |"), Err(ParseError::UnexpectedToken("|")));
+        assert_eq!(parse("ls &&"), Err(ParseError::UnexpectedToken("&&")));
+        assert_eq!(parse("ls ||"), Err(ParseError::UnexpectedToken("||")));
+        assert_eq!(parse("a && ;"), Err(ParseError::UnexpectedToken(";")));
+        assert_eq!(parse("| ls"), Err(ParseError::UnexpectedToken("|")));
+    }
+
+    #[test]
+    fn lone_ampersand_is_text() {
+        assert_eq!(words("echo a & b"), ["echo", "a", "&", "b"]);
+        assert_eq!(words("echo a&b"), ["echo", "a&b"]);
+        assert_eq!(words("a&&b"), ["a", "&&", "b"]);
+    }
+
+    #[test]
+    fn quotes_and_escapes() {
+        assert_eq!(
+            words(r#"echo 'a  b' "c \"d\" \\" e\ f "" 'x|y;z'"#),
+            ["echo", "a  b", r#"c "d" \"#, "e f", "", "x|y;z"]
+        );
+        // Only `\"` and `\\` are escapes inside double quotes.
+        assert_eq!(words(r#""a\nb""#), [r"a\nb"]);
+        assert_eq!(words(r#"'a\"b'"#), [r#"a\"b"#]);
+        assert_eq!(words("a'b'\"c\""), ["abc"]);
+    }
+
+    #[test]
+    fn reports_unfinished_input() {
+        assert_eq!(
+            tokenize("echo 'a"),
+            Err(ParseError::UnterminatedQuote('\''))
+        );
+        assert_eq!(
+            tokenize("echo \"a"),
+            Err(ParseError::UnterminatedQuote('"'))
+        );
+        assert_eq!(
+            tokenize("echo \"a\\"),
+            Err(ParseError::UnterminatedQuote('"'))
+        );
+        assert_eq!(tokenize("echo a\\"), Err(ParseError::TrailingEscape));
+    }
+
+    #[test]
+    fn splits_flags_and_positionals() {
+        let args = args("-lv --all file - --", &[]);
+        assert_eq!(args.positional(), ["file", "-"]);
+        assert!(args.has_flag('l', "long"));
+        assert!(args.has_flag('v', "verbose"));
+        assert!(args.has_flag('a', "all"));
+        assert!(!args.has_flag('x', "extra"));
+    }
+
+    #[test]
+    fn double_dash_ends_flags() {
+        let args = args("-i -- -v --help", &[]);
+        assert_eq!(args.positional(), ["-v", "--help"]);
+        assert!(args.has_flag('i', "ignore-case"));
+        assert!(!args.has_flag('h', "help"));
+    }
+
+    #[test]
+    fn option_values() {
+        let options = &[('n', "lines")];
+        for input in ["-n5", "-n 5", "--lines=5", "--lines 5", "-n 1 -n 5"] {
+            let parsed = args(input, options);
+            assert_eq!(parsed.value("lines"), Some("5"), "{input}");
+            assert!(parsed.positional().is_empty(), "{input}");
+        }
+        // Flags before the option in the same word still count.
+        let parsed = args("-vn3 file", &[('n', "lines")]);
+        assert!(parsed.has_flag('v', "verbose"));
+        assert_eq!(parsed.value("lines"), Some("3"));
+        assert_eq!(parsed.first(), Some("file"));
+        // Without a declared option, `-n5` is two flags.
+        assert!(args("-n5", &[]).has_flag('5', "five"));
+    }
+
+    #[test]
+    fn rejects_unknown_flags() {
+        let parsed = args("-l --color x", &[]);
+        assert_eq!(
+            parsed.allow_flags(&[('l', "long")]),
+            Err(CommandError::UnknownFlag("color".to_string()))
+        );
+        assert_eq!(
+            CommandError::UnknownFlag("color".to_string()).to_string(),
+            "unknown option: --color"
+        );
+        assert_eq!(args("a b", &[]).at_most(1), Err(CommandError::Usage));
+        assert_eq!(
+            args("-x", &[]).at_most(1),
+            Err(CommandError::UnknownFlag("x".to_string()))
+        );
+    }
+}
@@ -0,0 +1,298 @@
+use serde::{Deserialize, Serialize};
+
+use super::output::Block;
+use super::parser::{parse, Args, CommandError, Operator};
+use crate::blog::Visibility;
+
+/// What a command wants the terminal to do once it has run.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Output {
+    /// Blocks printed to the scrollback.
+    Print(Vec<Block>),
+    /// A link that is printed and opened in a new tab.
+    Link { url: String, name: String },
+    /// Wipe the scrollback.
+    Clear,
+    /// Make the given absolute path the working directory.
+    ChangeDir(String),
+    /// Print a line and route to a page of this site in-app.
+    Navigate { path: String, message: String },
+}
+
+impl Output {
+    pub fn text(text: impl Into<String>) -> Self {
+        Output::Print(vec![Block::Text(text.into())])
+    }
+
+    /// What the output looks like when piped into another command. Effects
+    /// such as changing directory do not happen inside a pipe.
+    pub fn plain_text(&self) -> String {
+        match self {
+            Output::Print(blocks) => blocks
+                .iter()
+                .map(Block::plain_text)
+                .collect::<Vec<_>>()
+                .join("\n"),
+            Output::Link { url, .. } => url.clone(),
+            Output::Navigate { message, .. } => message.clone(),
+            Output::Clear | Output::ChangeDir(_) => String::new(),
+        }
+    }
+}
+
+/// What the terminal shows of the site, which depends on the visitor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Environment {
+    /// The posts under `/blog` and in `blog` and `search`.
+    pub visibility: Visibility,
+    /// The site's origin for absolute links, see `SITE_ORIGIN`.
+    pub origin: String,
+}
+
+/// State handed to a command when it executes.
+pub struct CommandContext<'a> {
+    pub registry: &'a CommandRegistry,
+    pub env: &'a Environment,
+    /// Absolute working directory in the virtual filesystem.
+    pub cwd: &'a str,
+    /// Text piped in from the previous command, if any.
+    pub stdin: Option<&'a str>,
+}
+
+/// A command that can be registered with the terminal.
+pub trait ShellCommand: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    /// One line shown by `help`.
+    fn description(&self) -> &'static str;
+
+    fn usage(&self) -> &'static str {
+        self.name()
+    }
+
+    /// Flags that take no value, as `(short, long)`.
+    fn flags(&self) -> &'static [(char, &'static str)] {
+        &[]
+    }
+
+    /// Options that take a value, as `(short, long)`.
+    fn options(&self) -> &'static [(char, &'static str)] {
+        &[]
+    }
+
+    /// Whether the command needs what only the server has, e.g. the search
+    /// index. The browser sends lines that use it to [`run_on_server`].
+    ///
+    /// [`run_on_server`]: super::session::run_on_server
+    fn on_server(&self) -> bool {
+        false
+    }
+
+    fn execute(&self, args: &Args, ctx: &CommandContext) -> Result<Output, CommandError>;
+
+    /// Candidates for the argument being typed after `args`. The caller filters
+    /// them by `partial`, so returning every valid value is fine.
+    fn complete(&self, _args: &[String], _partial: &str, _ctx: &CommandContext) -> Vec<String> {
+        Vec::new()
+    }
+}
+
+#[derive(Default)]
+pub struct CommandRegistry {
+    commands: Vec<Box<dyn ShellCommand>>,
+}
+
+impl CommandRegistry {
+    pub fn register(&mut self, command: impl ShellCommand + 'static) -> &mut Self {
+        self.commands.push(Box::new(command));
+        self
+    }
+
+    /// Looks up a command by name or alias, ignoring case.
+    pub fn find(&self, name: &str) -> Option<&dyn ShellCommand> {
+        let name = name.to_lowercase();
+        self.commands
+            .iter()
+            .find(|command| command.name() == name || command.aliases().contains(&name.as_str()))
+            .map(|command| command.as_ref())
+    }
+
+    /// Registered commands in registration order.
+    pub fn iter(&self) -> impl Iterator<Item = &dyn ShellCommand> {
+        self.commands.iter().map(|command| command.as_ref())
+    }
+
+    /// Whether any command in `input` has to run on the server.
+    pub fn needs_server(&self, input: &str) -> bool {
+        parse(input).is_ok_and(|steps| {
+            steps
+                .iter()
+                .flat_map(|step| &step.pipeline)
+                .filter_map(|words| self.find(words.first()?))
+                .any(|command| command.on_server())
+        })
+    }
+
+    /// Runs one line of input against the registry, returning the output of
+    /// every command that ran in order.
+    ///
+    /// Parse and usage errors are reported as text rather than failing, the
+    /// same way a shell prints them to stderr. Directory changes apply to the
+    /// commands after them on the same line.
+    pub fn run(&self, input: &str, cwd: &str, env: &Environment) -> Vec<Output> {
+        let steps = match parse(input) {
+            Ok(steps) => steps,
+            Err(err) => return vec![Output::text(format!("rabbitnook: {err}"))],
+        };
+
+        let mut cwd = cwd.to_string();
+        let mut outputs = Vec::new();
+        let mut succeeded = true;
+        for step in steps {
+            let skip = match step.condition {
+                Operator::And => !succeeded,
+                Operator::Or => succeeded,
+                Operator::Pipe | Operator::Then => false,
+            };
+            if skip {
+                continue;
+            }
+
+            let mut stdin: Option<String> = None;
+            let mut commands = step.pipeline.iter().peekable();
+            while let Some(words) = commands.next() {
+                let result = self.execute(words, &cwd, stdin.as_deref(), env);
+                if commands.peek().is_some() {
+                    // Errors go straight to the screen, like stderr.
+                    stdin = Some(match result {
+                        Ok(output) => output.plain_text(),
+                        Err(error) => {
+                            outputs.push(error);
+                            String::new()
+                        }
+                    });
+                    continue;
+                }
+
+                succeeded = result.is_ok();
+                let output = result.unwrap_or_else(|error| error);
+                if let Output::ChangeDir(dir) = &output {
+                    cwd = dir.clone();
+                }
+                outputs.push(output);
+            }
+        }
+        outputs
+    }
+
+    /// Runs a single command, with the error message as `Err` if it failed.
+    fn execute(
+        &self,
+        words: &[String],
+        cwd: &str,
+        stdin: Option<&str>,
+        env: &Environment,
+    ) -> Result<Output, Output> {
+        let Some((name, rest)) = words.split_first() else {
+            return Ok(Output::Print(Vec::new()));
+        };
+        let Some(command) = self.find(name) else {
+            return Err(Output::text(format!("{name}: command not found")));
+        };
+
+        let args = Args::parse(rest, command.options());
+        if args.has_flag('h', "help") {
+            return Ok(Output::text(format!("usage: {}", command.usage())));
+        }
+
+        let ctx = CommandContext {
+            registry: self,
+            env,
+            cwd,
+            stdin,
+        };
+        command.execute(&args, &ctx).map_err(|err| match err {
+            CommandError::Usage => Output::text(format!("usage: {}", command.usage())),
+            err => Output::text(format!("{}: {err}", command.name())),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::terminal::commands::COMMANDS;
+
+    fn env() -> Environment {
+        Environment {
+            visibility: serde_json::from_str(r#"{"today":"2024-06-01","preview":false}"#).unwrap(),
+            origin: "https://example.com".to_string(),
+        }
+    }
+
+    fn run(input: &str) -> Vec<Output> {
+        COMMANDS.run(input, "/", &env())
+    }
+
+    fn text(input: &str) -> Vec<String> {
+        run(input).iter().map(Output::plain_text).collect()
+    }
+
+    #[test]
+    fn or_runs_after_a_failure() {
+        assert_eq!(
+            text("cat missing || echo x"),
+            ["cat: missing: No such file or directory", "x"]
+        );
+        assert_eq!(text("echo a || echo b"), ["a"]);
+    }
+
+    #[test]
+    fn and_stops_at_a_failure() {
+        assert_eq!(
+            text("cat missing && echo x"),
+            ["cat: missing: No such file or directory"]
+        );
+        assert_eq!(text("echo a && echo b"), ["a", "b"]);
+        // A skipped step keeps the last status for the ones after it.
+        assert_eq!(
+            text("cat missing && echo a || echo b"),
+            ["cat: missing: No such file or directory", "b"]
+        );
+        assert_eq!(text("cat missing; echo x")[1], "x");
+    }
+
+    #[test]
+    fn cd_applies_to_the_rest_of_the_line() {
+        let outputs = run("cd /blog && ls");
+        assert_eq!(outputs[0], Output::ChangeDir("/blog".to_string()));
+        assert_eq!(outputs[1], run("ls /blog")[0]);
+        assert_eq!(text("cd projects; pwd")[1], "/projects");
+        // Not when the cd failed.
+        assert_eq!(text("cd nowhere || pwd")[1], "/");
+    }
+
+    #[test]
+    fn pipe_errors_go_to_the_screen() {
+        assert_eq!(
+            text("cat missing | wc -l"),
+            ["cat: missing: No such file or directory", "      0"]
+        );
+    }
+
+    #[test]
+    fn help_flag_prints_usage() {
+        assert_eq!(text("ls -h"), ["usage: ls [-l] [path]"]);
+        assert_eq!(text("head --help"), ["usage: head [-n lines] [file...]"]);
+        assert_eq!(text("ls -x"), ["ls: unknown option: -x"]);
+        assert_eq!(text("nope"), ["nope: command not found"]);
+        assert_eq!(
+            text("echo 'a"),
+            ["rabbitnook: unexpected end of input, missing closing '"]
+        );
+    }
+}
@@ -0,0 +1,464 @@
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::commands::COMMANDS;
+use super::output::{Block, Link};
+use super::registry::{Environment, Output};
+
+/// Scrollback entries kept before the oldest are dropped.
+pub const DEFAULT_SCROLLBACK: usize = 200;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Command {
+    /// Unique within the session, used to key the scrollback.
+    pub(super) id: u64,
+    /// Milliseconds since the Unix epoch.
+    pub(super) timestamp: u64,
+    pub(super) command: String,
+    /// Working directory the command was run in, for its prompt.
+    pub(super) cwd: String,
+    pub(super) output: Vec<Block>,
+}
+
+/// Something only the page can do once a command has run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Action {
+    OpenLink(String),
+    Navigate(String),
+}
+
+/// Everything the terminal shows: the working directory and the scrollback.
+///
+/// The same type runs commands in the browser and, for visitors without
+/// WASM, on the server.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Session {
+    pub(super) cwd: String,
+    pub(super) entries: Vec<Command>,
+    next_id: u64,
+}
+
+impl Default for Session {
+    fn default() -> Self {
+        Session {
+            cwd: "/".to_string(),
+            entries: Vec::new(),
+            next_id: 0,
+        }
+    }
+}
+
+impl Session {
+    /// Runs one line and records its output in the scrollback, returning
+    /// what is left for the page to do in the order the commands asked.
+    pub fn run(&mut self, input: &str, timestamp: u64, env: &Environment) -> Vec<Action> {
+        let cwd = self.cwd.clone();
+        let outputs = COMMANDS.run(input, &cwd, env);
+        self.record(input, cwd, timestamp, outputs)
+    }
+
+    /// Records the `outputs` of a line run in `cwd`, e.g. by [`run_on_server`],
+    /// like [`Session::run`] does.
+    pub fn record(
+        &mut self,
+        input: &str,
+        cwd: String,
+        timestamp: u64,
+        outputs: Vec<Output>,
+    ) -> Vec<Action> {
+        let mut blocks = Vec::new();
+        let mut actions = Vec::new();
+        let mut cleared = false;
+        for output in outputs {
+            match output {
+                Output::Clear => {
+                    self.entries.clear();
+                    blocks.clear();
+                    cleared = true;
+                }
+                Output::Print(printed) => blocks.extend(printed),
+                Output::ChangeDir(dir) => self.cwd = dir,
+                Output::Navigate { path, message } => {
+                    blocks.push(Block::Text(message));
+                    actions.push(Action::Navigate(path));
+                }
+                Output::Link { url, name } => {
+                    blocks.push(Block::Links(vec![Link {
+                        href: url.clone(),
+                        label: name,
+                    }]));
+                    actions.push(Action::OpenLink(url));
+                }
+            }
+        }
+
+        // A bare `clear` leaves an empty screen rather than its own prompt.
+        if !(cleared && blocks.is_empty()) {
+            self.entries.push(Command {
+                id: self.next_id,
+                timestamp,
+                command: input.to_string(),
+                cwd,
+                output: blocks,
+            });
+            self.next_id += 1;
+        }
+        actions
+    }
+
+    /// Adds an entry without running anything, e.g. completion candidates.
+    pub fn print(&mut self, command: &str, output: Vec<Block>, timestamp: u64) {
+        self.entries.push(Command {
+            id: self.next_id,
+            timestamp,
+            command: command.to_string(),
+            cwd: self.cwd.clone(),
+            output,
+        });
+        self.next_id += 1;
+    }
+
+    /// Drops the oldest entries beyond `max`.
+    pub fn truncate(&mut self, max: usize) {
+        let overflow = self.entries.len().saturating_sub(max);
+        self.entries.drain(..overflow);
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn now() -> u64 {
+    web_sys::js_sys::Date::now() as u64
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_millis() as u64)
+}
+
+/// Formats a timestamp as `HH:MM:SS UTC`. The server and the browser format
+/// it the same way, so hydrating the scrollback doesn't change it.
+pub fn clock(timestamp: u64) -> String {
+    let seconds = timestamp / 1000;
+    format!(
+        "{:02}:{:02}:{:02} UTC",
+        (seconds / 3600) % 24,
+        (seconds / 60) % 60,
+        seconds % 60
+    )
+}
+
+/// Runs a line the browser can't because one of its commands needs the
+/// server, returning what the commands output for [`Session::record`].
+#[server]
+pub async fn run_on_server(input: String, cwd: String) -> Result<Vec<Output>, ServerFnError> {
+    Ok(COMMANDS.run(&input, &cwd, &environment().await?))
+}
+
+/// The session a visitor without WASM has built up, replayed from their cookie.
+#[server]
+pub async fn load_session() -> Result<Session, ServerFnError> {
+    Ok(saved::read().replay(&environment().await?).0)
+}
+
+/// Runs a line posted by the terminal's `<form>` when WASM is not available.
+///
+/// The line is appended to the session cookie and the visitor is redirected
+/// back to the terminal, or to the page a command navigated to.
+#[server(prefix = "/api", endpoint = "terminal")]
+pub async fn run_terminal(input: String) -> Result<(), ServerFnError> {
+    let env = environment().await?;
+    let mut saved = saved::read();
+    let action = saved.push(now(), input, &env);
+    saved::write(saved, &env);
+
+    match action {
+        Some(Action::Navigate(path)) => leptos_axum::redirect(&path),
+        _ => leptos_axum::redirect("/"),
+    }
+    Ok(())
+}
+
+/// The environment of the visitor whose request is being answered.
+#[cfg(feature = "ssr")]
+async fn environment() -> Result<Environment, ServerFnError> {
+    Ok(Environment {
+        visibility: crate::blog::get_visibility().await?,
+        origin: crate::site::SITE_ORIGIN.clone(),
+    })
+}
+
+#[cfg(feature = "ssr")]
+mod saved {
+    use http::header::{COOKIE, SET_COOKIE};
+    use http::request::Parts;
+    use http::HeaderValue;
+    use leptos::prelude::*;
+    use leptos_axum::ResponseOptions;
+
+    use super::{Action, Environment, Session, DEFAULT_SCROLLBACK};
+    use crate::site::cookie_attributes;
+
+    const COOKIE_NAME: &str = "rabbitnook_terminal";
+    /// Keeps the cookie well under the 4 KB browsers allow.
+    const MAX_COOKIE_LEN: usize = 3000;
+
+    /// The lines a visitor typed, stored instead of the rendered output so the
+    /// cookie stays small.
+    pub struct SavedSession {
+        /// Working directory before the first line.
+        pub cwd: String,
+        /// Timestamp and text of each line.
+        pub lines: Vec<(u64, String)>,
+    }
+
+    impl SavedSession {
+        /// Reruns every line, returning the session and where the last line
+        /// navigated to, if anywhere.
+        pub fn replay(&self, env: &Environment) -> (Session, Option<Action>) {
+            let mut session = Session {
+                cwd: self.cwd.clone(),
+                ..Default::default()
+            };
+            let mut action = None;
+            for (timestamp, line) in &self.lines {
+                action = session
+                    .run(line, *timestamp, env)
+                    .into_iter()
+                    .rfind(|action| matches!(action, Action::Navigate(_)));
+            }
+            session.truncate(DEFAULT_SCROLLBACK);
+            (session, action)
+        }
+
+        /// Adds a line, returning where it navigated to, if anywhere.
+        pub fn push(&mut self, timestamp: u64, line: String, env: &Environment) -> Option<Action> {
+            self.lines.push((timestamp, line));
+            let (session, action) = self.replay(env);
+            if session.entries.is_empty() {
+                // `clear` ran, nothing before it needs replaying again.
+                self.cwd = session.cwd;
+                self.lines.clear();
+            }
+            action
+        }
+
+        /// Forgets the oldest lines until the cookie fits, carrying their
+        /// directory changes forward.
+        fn fit(&mut self, env: &Environment) {
+            while self.encode().len() > MAX_COOKIE_LEN && !self.lines.is_empty() {
+                let first = self.lines.remove(0);
+                self.cwd = SavedSession {
+                    cwd: std::mem::take(&mut self.cwd),
+                    lines: vec![first],
+                }
+                .replay(env)
+                .0
+                .cwd;
+            }
+        }
+
+        fn encode(&self) -> String {
+            let mut value = encode(&self.cwd);
+            for (timestamp, line) in &self.lines {
+                value.push('&');
+                value.push_str(&format!("{timestamp}:{}", encode(line)));
+            }
+            value
+        }
+
+        fn decode(value: &str) -> Option<Self> {
+            let mut parts = value.split('&');
+            let cwd = decode(parts.next()?)?;
+            let lines = parts
+                .map(|part| {
+                    let (timestamp, line) = part.split_once(':')?;
+                    Some((timestamp.parse().ok()?, decode(line)?))
+                })
+                .collect::<Option<_>>()?;
+            Some(SavedSession { cwd, lines })
+        }
+    }
+
+    pub fn read() -> SavedSession {
+        use_context::<Parts>()
+            .and_then(|parts| {
+                parts
+                    .headers
+                    .get_all(COOKIE)
+                    .iter()
+                    .filter_map(|header| header.to_str().ok())
+                    .flat_map(|header| header.split(';'))
+                    .find_map(|cookie| cookie.trim().strip_prefix(&format!("{COOKIE_NAME}=")))
+                    .and_then(SavedSession::decode)
+            })
+            .unwrap_or_else(|| SavedSession {
+                cwd: "/".to_string(),
+                lines: Vec::new(),
+            })
+    }
+
+    pub fn write(mut saved: SavedSession, env: &Environment) {
+        saved.fit(env);
+
+        let cookie = format!(
+            "{COOKIE_NAME}={}; Max-Age=86400; {}",
+            saved.encode(),
+            cookie_attributes()
+        );
+        if let (Some(response), Ok(cookie)) = (
+            use_context::<ResponseOptions>(),
+            HeaderValue::from_str(&cookie),
+        ) {
+            response.insert_header(SET_COOKIE, cookie);
+        }
+    }
+
+    /// Percent-encodes everything but unreserved characters, which keeps the
+    /// value safe inside a cookie.
+    fn encode(text: &str) -> String {
+        text.bytes()
+            .map(|byte| match byte {
+                b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                    (byte as char).to_string()
+                }
+                _ => format!("%{byte:02X}"),
+            })
+            .collect()
+    }
+
+    fn decode(text: &str) -> Option<String> {
+        let mut bytes = Vec::with_capacity(text.len());
+        let mut rest = text.as_bytes();
+        while let Some((&byte, tail)) = rest.split_first() {
+            if byte == b'%' {
+                let hex = tail
+                    .get(..2)
+                    .filter(|hex| hex.iter().all(u8::is_ascii_hexdigit))?;
+                bytes.push(u8::from_str_radix(std::str::from_utf8(hex).ok()?, 16).ok()?);
+                rest = &tail[2..];
+            } else {
+                bytes.push(byte);
+                rest = tail;
+            }
+        }
+        String::from_utf8(bytes).ok()
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::super::Block;
+        use super::*;
+
+        fn env() -> Environment {
+            Environment {
+                visibility: serde_json::from_str(r#"{"today":"2024-06-01","preview":false}"#)
+                    .unwrap(),
+                origin: "https://example.com".to_string(),
+            }
+        }
+
+        fn saved(cwd: &str, lines: &[&str]) -> SavedSession {
+            SavedSession {
+                cwd: cwd.to_string(),
+                lines: lines
+                    .iter()
+                    .enumerate()
+                    .map(|(i, line)| (i as u64, line.to_string()))
+                    .collect(),
+            }
+        }
+
+        #[test]
+        fn round_trips() {
+            let original = saved(
+                "/blog & co",
+                &[
+                    "echo a&b",
+                    "echo 1:2 50% done; ls",
+                    "echo 'åäö' ❤️ \"x\"",
+                    "",
+                ],
+            );
+            let value = original.encode();
+            assert!(
+                value
+                    .bytes()
+                    .all(|byte| byte.is_ascii_alphanumeric() || b"-._~%&:".contains(&byte)),
+                "{value}"
+            );
+            let decoded = SavedSession::decode(&value).unwrap();
+            assert_eq!(decoded.cwd, original.cwd);
+            assert_eq!(decoded.lines, original.lines);
+        }
+
+        #[test]
+        fn decode_rejects_malformed_values() {
+            for value in [
+                "%", "%4", "%zz", "%+f", "/&ls", "/&x:ls", "/&1:%", "/&1:%FF", "%C3",
+            ] {
+                assert!(SavedSession::decode(value).is_none(), "{value}");
+            }
+            assert_eq!(SavedSession::decode("").unwrap().cwd, "");
+        }
+
+        #[test]
+        fn fit_drops_the_oldest_lines_but_keeps_their_cwd() {
+            let long = format!("echo {}", "x".repeat(500));
+            let mut lines = vec!["cd blog", "cd ..", "cd projects"];
+            lines.extend(std::iter::repeat_n(long.as_str(), 7));
+            let mut session = saved("/", &lines);
+            session.fit(&env());
+            assert!(session.encode().len() <= MAX_COOKIE_LEN);
+            assert_eq!(session.cwd, "/projects");
+            assert_eq!(session.lines.len(), 5);
+            assert!(session.lines.iter().all(|(_, line)| *line == long));
+
+            let mut small = saved("/blog", &["ls"]);
+            small.fit(&env());
+            assert_eq!(small.lines.len(), 1);
+        }
+
+        #[test]
+        fn replays_lines() {
+            let (session, action) = saved("/", &["cd blog", "pwd", "cd .."]).replay(&env());
+            assert_eq!(session.cwd, "/");
+            assert_eq!(session.entries.len(), 3);
+            assert_eq!(
+                session.entries[1].output,
+                [Block::Text("/blog".to_string())]
+            );
+            assert_eq!(action, None);
+        }
+
+        #[test]
+        fn clear_starts_an_empty_scrollback() {
+            let mut session = saved("/", &["cd blog", "ls"]);
+            session.push(2, "clear".to_string(), &env());
+            assert_eq!(session.cwd, "/blog");
+            assert!(session.lines.is_empty());
+
+            session.push(3, "pwd".to_string(), &env());
+            let (replayed, _) = session.replay(&env());
+            assert_eq!(replayed.entries.len(), 1);
+            assert_eq!(replayed.entries[0].command, "pwd");
+
+            // Output after `clear` on the same line stays.
+            let (replayed, _) = saved("/", &["ls", "clear; pwd"]).replay(&env());
+            assert_eq!(replayed.entries.len(), 1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::clock;
+
+    #[test]
+    fn clock_is_utc() {
+        assert_eq!(clock(0), "00:00:00 UTC");
+        // 2024-03-01T23:59:59.999Z
+        assert_eq!(clock(1_709_337_599_999), "23:59:59 UTC");
+    }
+}
@@ -0,0 +1,99 @@
+use leptos::ev;
+use leptos::prelude::*;
+
+use crate::blog::markdown::TocEntry;
+use crate::components::code_block::{use_copy, UseCopyReturn};
+
+/// How far below the top of the window a heading counts as the current one,
+/// in pixels.
+const SPY_OFFSET: f64 = 96.0;
+
+/// Links to the sections of a post. The section being read is highlighted
+/// once the page has hydrated.
+#[component]
+pub fn TableOfContents(entries: Vec<TocEntry>) -> impl IntoView {
+    let (active, set_active) = signal(None::<String>);
+    let ids: Vec<String> = entries.iter().map(|entry| entry.id.clone()).collect();
+    let spy = move || {
+        // The last heading scrolled past, or none above the first one.
+        let current = ids
+            .iter()
+            .take_while(|id| {
+                document()
+                    .get_element_by_id(id)
+                    .is_some_and(|heading| heading.get_bounding_client_rect().top() <= SPY_OFFSET)
+            })
+            .last()
+            .cloned();
+        if active.get_untracked() != current {
+            set_active.set(current);
+        }
+    };
+    Effect::new({
+        let spy = spy.clone();
+        move |_| spy()
+    });
+    let handle = window_event_listener(ev::scroll, move |_| spy());
+    on_cleanup(move || handle.remove());
+
+    view! {
+        <nav class="text-sm" aria-label="Table of contents">
+            <p class="mb-2 text-peach">"Contents"</p>
+            <ul>
+                {entries
+                    .into_iter()
+                    .map(|entry| {
+                        let id = entry.id.clone();
+                        let is_active = move || active.get().as_deref() == Some(id.as_str());
+                        let indent = if entry.level > 2 { "ml-4" } else { "" };
+                        view! {
+                            <li class=indent>
+                                <a
+                                    class="hover:text-maroon"
+                                    class=("text-maroon", is_active)
+                                    href=format!("#{}", entry.id)
+                                >
+                                    {entry.title}
+                                </a>
+                            </li>
+                        }
+                    })
+                    .collect_view()}
+            </ul>
+        </nav>
+    }
+}
+
+/// A `#` link to the heading with `id`, plus a button copying its full URL
+/// where the clipboard is available. Both show when the heading is hovered.
+#[component]
+pub fn HeadingLinks(id: String) -> impl IntoView {
+    let UseCopyReturn {
+        can_copy,
+        copied,
+        copy,
+    } = use_copy();
+
+    let copy = {
+        let id = id.clone();
+        move |_| {
+            let location = window().location();
+            if let (Ok(origin), Ok(path)) = (location.origin(), location.pathname()) {
+                copy(&format!("{origin}{path}#{id}"));
+            }
+        }
+    };
+
+    view! {
+        <span class="ml-2 text-base opacity-0 group-hover:opacity-100 focus-within:opacity-100">
+            <a class="hover:text-maroon" href=format!("#{id}") aria-label="Link to this section">
+                "#"
+            </a>
+            <Show when=move || can_copy.get()>
+                <button class="ml-2 text-xs hover:text-maroon" on:click=copy.clone()>
+                    {move || if copied.get() { "Copied!" } else { "Copy link" }}
+                </button>
+            </Show>
+        </span>
+    }
+}
@@ -1,5 +1,10 @@
 pub mod app;
+mod blog;
 mod components;
+pub mod site;
+
+#[cfg(feature = "ssr")]
+pub use blog::{feed, images, og_image, sitemap};
 
 #[cfg(feature = "hydrate")]
 #[wasm_bindgen::prelude::wasm_bindgen]
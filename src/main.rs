@@ -1,19 +1,31 @@
 #[cfg(feature = "ssr")]
 #[tokio::main]
 async fn main() {
+    use axum::routing::get;
     use axum::Router;
     use leptos::logging::log;
     use leptos::prelude::*;
     use leptos_axum::{generate_route_list, LeptosRoutes};
     use rabbitnook::app::{shell, App};
+    use rabbitnook::{feed, images, og_image, sitemap};
 
     let conf = get_configuration(None).unwrap();
     let addr = conf.leptos_options.site_addr;
     let leptos_options = conf.leptos_options;
     // Generate the list of routes in your Leptos App
     let routes = generate_route_list(App);
+    let sitemap_paths = sitemap::static_paths(&routes);
 
     let app = Router::new()
+        .route("/blog/rss.xml", get(feed::rss))
+        .route("/blog/atom.xml", get(feed::atom))
+        .route("/blog/og/{file}", get(og_image::og_image))
+        .route("/blog/img/{file}", get(images::variant))
+        .route(
+            "/sitemap.xml",
+            get(move || sitemap::sitemap(sitemap_paths.clone())),
+        )
+        .route("/robots.txt", get(sitemap::robots))
         .leptos_routes(&leptos_options, routes, {
             let leptos_options = leptos_options.clone();
             move || shell(leptos_options.clone())
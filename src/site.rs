@@ -0,0 +1,38 @@
+//! Settings for the whole site.
+
+use leptos::prelude::*;
+
+pub const SITE_NAME: &str = "RabbitNook";
+
+pub const AUTHOR: &str = "Oliver Säfström";
+
+#[cfg(feature = "ssr")]
+lazy_static::lazy_static! {
+    /// Origin used for absolute URLs, from `SITE_ORIGIN` without a trailing `/`.
+    pub static ref SITE_ORIGIN: String = std::env::var("SITE_ORIGIN")
+        .unwrap_or_else(|_| "https://rabbitnook.com".to_string())
+        .trim_end_matches('/')
+        .to_string();
+}
+
+/// Attributes of every cookie the server sets. `Secure` keeps them off plain
+/// HTTP when the site is served over HTTPS.
+#[cfg(feature = "ssr")]
+pub fn cookie_attributes() -> &'static str {
+    if SITE_ORIGIN.starts_with("https://") {
+        "Path=/; HttpOnly; SameSite=Lax; Secure"
+    } else {
+        "Path=/; HttpOnly; SameSite=Lax"
+    }
+}
+
+/// [`SITE_ORIGIN`], which only the server's environment knows.
+#[server]
+pub async fn get_site_origin() -> Result<String, ServerFnError> {
+    Ok(SITE_ORIGIN.clone())
+}
+
+/// The site origin, provided by `App`.
+pub fn use_site_origin() -> Resource<String> {
+    expect_context()
+}
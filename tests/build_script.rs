@@ -0,0 +1,11 @@
+//! Runs the tests of the modules `build.rs` is made of, since Cargo doesn't
+//! test build scripts.
+
+#[path = "../src/blog/fnv.rs"]
+mod fnv;
+#[path = "../build/front_matter.rs"]
+mod front_matter;
+#[path = "../build/images.rs"]
+mod images;
+#[path = "../build/validate.rs"]
+mod validate;